@@ -0,0 +1,202 @@
+//! 输入动作模块
+//! 把键盘按键映射为游戏中的动作，所有系统只判断动作，不直接判断按键
+//!
+//! 按键绑定保存在设置文件中（见 [`SettingsPlugin`](crate::SettingsPlugin)），
+//! 可以在设置界面的按键设置界面中修改（见 [`ControlsPlugin`](crate::ControlsPlugin)）
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::i18n::Language;
+
+/// 游戏中的输入动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Serialize, Deserialize)]
+pub enum Action {
+    /// 挡板向左移动，关卡选择界面中翻到上一页
+    MoveLeft,
+    /// 挡板向右移动，关卡选择界面中翻到下一页
+    MoveRight,
+    /// 发射小球或激光
+    Launch,
+    /// 暂停或继续游戏
+    Pause,
+    /// 确认（进入选中的关卡）
+    Confirm,
+    /// 返回上一个界面
+    Back,
+}
+
+impl Action {
+    /// 所有动作，按键设置界面按这个顺序显示
+    pub const ALL: [Action; 6] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Launch,
+        Action::Pause,
+        Action::Confirm,
+        Action::Back,
+    ];
+
+    /// 动作名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "向左移动",
+            Action::MoveRight => "向右移动",
+            Action::Launch => "发射",
+            Action::Pause => "暂停",
+            Action::Confirm => "确认",
+            Action::Back => "返回",
+        }
+    }
+
+    /// 动作是否在游戏进行中使用
+    ///
+    /// 同时生效的动作之间不能共用按键：游戏中的动作之间、界面中的动作之间分别检查冲突
+    pub fn in_gameplay(self) -> bool {
+        matches!(self, Action::MoveLeft | Action::MoveRight | Action::Launch | Action::Pause)
+    }
+
+    /// 默认绑定的按键
+    pub fn default_keys(self) -> Vec<KeyCode> {
+        match self {
+            Action::MoveLeft => vec![KeyCode::ArrowLeft],
+            Action::MoveRight => vec![KeyCode::ArrowRight],
+            Action::Launch => vec![KeyCode::ArrowUp],
+            Action::Pause => vec![KeyCode::Escape, KeyCode::KeyP],
+            Action::Confirm => vec![KeyCode::Enter, KeyCode::Space],
+            Action::Back => vec![KeyCode::Escape],
+        }
+    }
+}
+
+/// 按键绑定资源：每个动作对应的按键（一个动作可以绑定多个按键）
+///
+/// # 说明
+/// 设置文件中缺少的动作使用默认按键
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<Action, Vec<KeyCode>>", into = "BTreeMap<Action, Vec<KeyCode>>")]
+pub struct KeyBindings {
+    keys: BTreeMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::from(BTreeMap::new())
+    }
+}
+
+impl From<BTreeMap<Action, Vec<KeyCode>>> for KeyBindings {
+    fn from(mut keys: BTreeMap<Action, Vec<KeyCode>>) -> Self {
+        for action in Action::ALL {
+            keys.entry(action).or_insert_with(|| action.default_keys());
+        }
+        Self { keys }
+    }
+}
+
+impl From<KeyBindings> for BTreeMap<Action, Vec<KeyCode>> {
+    fn from(bindings: KeyBindings) -> Self {
+        bindings.keys
+    }
+}
+
+impl KeyBindings {
+    /// 动作绑定的按键
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// 动作的任意一个按键是否处于按下状态
+    pub fn pressed(&self, action: Action, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_pressed(self.keys(action).iter().copied())
+    }
+
+    /// 动作的任意一个按键是否在本帧刚按下
+    pub fn just_pressed(&self, action: Action, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_just_pressed(self.keys(action).iter().copied())
+    }
+
+    /// 把动作重新绑定到一个按键
+    ///
+    /// # 说明
+    /// 同时生效的其他动作（见 [`Action::in_gameplay`]）如果也绑定了这个按键，会从它们的绑定中移除；
+    /// 移除后没有按键的动作改为使用本动作原来的按键（相当于交换两个动作的按键）
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        let previous = self.keys.insert(action, vec![key]).unwrap_or_default();
+        for (&other, keys) in &mut self.keys {
+            if other == action || other.in_gameplay() != action.in_gameplay() {
+                continue;
+            }
+            keys.retain(|bound| *bound != key);
+            if keys.is_empty() {
+                keys.extend(previous.iter().copied().filter(|bound| *bound != key));
+            }
+        }
+    }
+
+    /// 动作绑定的按键名称，多个按键用“/”分隔，没有绑定按键时显示“未绑定”
+    pub fn label(&self, action: Action, language: Language) -> String {
+        let names: Vec<_> = self.keys(action).iter().map(|key| key_name(*key, language)).collect();
+        if names.is_empty() { language.tr("未绑定").to_string() } else { names.join(" / ") }
+    }
+}
+
+/// 按键的显示名称，文字名称按界面语言翻译
+pub fn key_name(key: KeyCode, language: Language) -> String {
+    match key {
+        KeyCode::ArrowLeft => "←".to_string(),
+        KeyCode::ArrowRight => "→".to_string(),
+        KeyCode::ArrowUp => "↑".to_string(),
+        KeyCode::ArrowDown => "↓".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Enter | KeyCode::NumpadEnter => language.tr("回车").to_string(),
+        KeyCode::Space => language.tr("空格").to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Backspace => language.tr("退格").to_string(),
+        _ => {
+            // KeyA → A，Digit1 → 1，其他按键使用枚举名称
+            let name = format!("{key:?}");
+            name.strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .map(str::to_string)
+                .unwrap_or(name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_swaps_conflicting_gameplay_keys() {
+        let mut bindings = KeyBindings::default();
+        bindings.rebind(Action::MoveLeft, KeyCode::ArrowRight);
+        assert_eq!(bindings.keys(Action::MoveLeft), [KeyCode::ArrowRight]);
+        assert_eq!(bindings.keys(Action::MoveRight), [KeyCode::ArrowLeft]);
+
+        // 暂停还有 P 键，只移除冲突的 Esc；界面中的返回键不受影响
+        bindings.rebind(Action::Launch, KeyCode::Escape);
+        assert_eq!(bindings.keys(Action::Pause), [KeyCode::KeyP]);
+        assert_eq!(bindings.keys(Action::Back), [KeyCode::Escape]);
+    }
+
+    #[test]
+    fn missing_actions_use_default_keys() {
+        let bindings: KeyBindings = ron::from_str("{MoveLeft: [KeyA]}").unwrap();
+        assert_eq!(bindings.keys(Action::MoveLeft), [KeyCode::KeyA]);
+        assert_eq!(bindings.keys(Action::Pause), Action::Pause.default_keys().as_slice());
+        assert_eq!(bindings.label(Action::Pause, Language::Chinese), "Esc / P");
+    }
+
+    #[test]
+    fn key_labels_follow_the_interface_language() {
+        let mut bindings = KeyBindings::default();
+        bindings.rebind(Action::Launch, KeyCode::Space);
+        assert_eq!(bindings.label(Action::Launch, Language::Chinese), "空格");
+        assert_eq!(bindings.label(Action::Launch, Language::English), "Space");
+        bindings.keys.insert(Action::Confirm, Vec::new());
+        assert_eq!(bindings.label(Action::Confirm, Language::English), "Unbound");
+    }
+}
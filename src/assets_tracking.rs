@@ -2,9 +2,9 @@
 
 use std::collections::VecDeque;
 
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*};
 
-pub(super) fn plugin(app: &mut App) {
+pub fn plugin(app: &mut App) {
     app.init_resource::<ResourceHandles>();  // 初始化资源追踪器
     app.add_systems(PreUpdate, load_resource_assets);  // 注册加载检查系统
 }
@@ -14,6 +14,14 @@ pub trait LoadResource {
     /// have been loaded, it will be inserted as a resource. This ensures that the resource only
     /// exists when the assets are ready.
     fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self;
+
+    /// Loads the [`Resource`] from an asset file at `path` using its registered [`AssetLoader`].
+    /// Like [`LoadResource::load_resource`], it is only inserted once the asset and all of its
+    /// dependencies have been loaded.
+    fn load_resource_from_path<T: Resource + Asset + Clone>(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> &mut Self;
 }
 
 impl LoadResource for App {
@@ -30,16 +38,33 @@ impl LoadResource for App {
         let mut handles = world.resource_mut::<ResourceHandles>();
         handles
             .waiting
-            .push_back((handle.untyped(), |world, handle| {
-                let assets = world.resource::<Assets<T>>();
-                if let Some(value) = assets.get(handle.id().typed::<T>()) {
-                    world.insert_resource(value.clone());
-                }
-            }));
+            .push_back((handle.untyped(), insert_loaded_resource::<T>));
+        self
+    }
+
+    fn load_resource_from_path<T: Resource + Asset + Clone>(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> &mut Self {
+        // 与 load_resource 不同，这里的资产由文件加载器产生，而不是由 FromWorld 构造
+        let world = self.world_mut();
+        let handle: Handle<T> = world.resource::<AssetServer>().load(path);
+        let mut handles = world.resource_mut::<ResourceHandles>();
+        handles
+            .waiting
+            .push_back((handle.untyped(), insert_loaded_resource::<T>));
         self
     }
 }
 
+/// 将已加载完成的资产克隆后作为资源插入
+fn insert_loaded_resource<T: Resource + Asset + Clone>(world: &mut World, handle: &UntypedHandle) {
+    let assets = world.resource::<Assets<T>>();
+    if let Some(value) = assets.get(handle.id().typed::<T>()) {
+        world.insert_resource(value.clone());
+    }
+}
+
 /// A function that inserts a loaded resource.
 type InsertLoadedResource = fn(&mut World, &UntypedHandle);
 
@@ -0,0 +1,357 @@
+//! 音频模块
+//! 加载游戏音效，在游戏事件发生时按音量设置播放对应的音效，并按游戏状态播放背景音乐
+//!
+//! - 每种游戏事件对应一个 [`SoundCue`]，音量为主音量 × 音效音量；碰撞由 [`CollisionAudio`] 播放，其他事件由 [`play_cue`] 播放
+//! - 小球碰撞音效（挡板、墙壁、砖块受损和摧毁）按击中位置在左右声道之间平移，音高随小球速度变化，
+//!   同时播放的碰撞音效数量有上限
+//! - 每个游戏状态对应一首循环播放的背景音乐（见 [`MusicTrack::for_state`]），
+//!   切换状态时旧音乐淡出、新音乐淡入，音量为主音量 × 音乐音量，修改设置后立即生效
+
+use bevy::audio::{SpatialScale, Volume};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::assets_tracking::LoadResource;
+use crate::config::GameConfig;
+use crate::components::{
+    BallHitPaddle, BallHitWall, BallLaunched, BrickDamaged, BrickDestroyed, GameSounds, LevelCleared, LifeLost,
+    MusicTrack, SoundCue,
+};
+use crate::settings::AudioSettings;
+use crate::state::GameState;
+
+/// 背景音乐淡入淡出的时长（秒）
+pub const MUSIC_CROSSFADE_SECS: f32 = 1.0;
+/// 同时播放的碰撞音效数量上限，超过时新的碰撞不再播放音效
+pub const MAX_COLLISION_VOICES: usize = 4;
+/// 碰撞音效随小球速度变化的最低音高倍数
+pub const MIN_COLLISION_PITCH: f32 = 0.8;
+/// 碰撞音效随小球速度变化的最高音高倍数
+pub const MAX_COLLISION_PITCH: f32 = 1.5;
+
+/// 音频插件
+/// 通过 assets_tracking 异步加载音效资源，注册播放音效的观察者和背景音乐系统
+///
+/// # 说明
+/// 依赖 `assets_tracking::plugin`，需要在它之后添加
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>()
+            // 使用 assets_tracking 异步加载音效资源
+            .load_resource::<GameSounds>()
+            // 每种游戏事件对应一个音效
+            .add_observer(play_paddle_hit)
+            .add_observer(play_wall_hit)
+            .add_observer(play_brick_damaged)
+            .add_observer(play_brick_break)
+            .add_observer(play_on::<LifeLost>(SoundCue::LifeLost))
+            .add_observer(play_on::<BallLaunched>(SoundCue::Launch))
+            .add_observer(play_on::<LevelCleared>(SoundCue::Victory))
+            .add_systems(OnEnter(GameState::GameOver), play_game_over_sound)
+            // 音效加载完成后开始播放当前状态的背景音乐
+            .add_systems(
+                Update,
+                (
+                    update_listener.run_if(resource_changed::<GameConfig>),
+                    switch_music.run_if(state_changed::<GameState>.or(resource_added::<GameSounds>)),
+                    fade_music,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// 背景音乐组件
+/// 标记正在播放背景音乐的实体，记录淡入淡出进度
+#[derive(Component, Debug)]
+pub struct Music {
+    /// 播放的背景音乐
+    pub track: MusicTrack,
+    /// 淡入淡出进度，0.0 为静音，1.0 为设置中的音乐音量
+    pub fade: f32,
+    /// 是否正在淡出，淡出结束后销毁实体
+    pub fading_out: bool,
+}
+
+/// 碰撞音效组件
+/// 标记正在播放的碰撞音效，用于限制同时播放的数量
+#[derive(Component, Debug)]
+pub struct CollisionVoice;
+
+/// 按小球速度计算碰撞音效的音高倍数
+///
+/// # 参数
+/// - `speed`: 小球的速度大小
+/// - `base_speed`: 配置中的小球速度
+///
+/// # 返回值
+/// 速度等于配置速度时为 1.0，速度越快音高越高，限制在 [`MIN_COLLISION_PITCH`] 到 [`MAX_COLLISION_PITCH`] 之间
+pub fn collision_pitch(speed: f32, base_speed: f32) -> f32 {
+    if base_speed <= 0.0 {
+        return 1.0;
+    }
+    (speed / base_speed).sqrt().clamp(MIN_COLLISION_PITCH, MAX_COLLISION_PITCH)
+}
+
+/// 播放碰撞音效需要的系统参数
+#[derive(SystemParam)]
+pub struct CollisionAudio<'w, 's> {
+    commands: Commands<'w, 's>,
+    /// 音效资源（加载完成前不存在）
+    sounds: Option<Res<'w, GameSounds>>,
+    audio_settings: Res<'w, AudioSettings>,
+    /// 游戏配置，决定左右声道对应的墙壁位置和正常的小球速度
+    config: Res<'w, GameConfig>,
+    /// 正在播放的碰撞音效
+    voices: Query<'w, 's, (), With<CollisionVoice>>,
+}
+
+impl CollisionAudio<'_, '_> {
+    /// 在碰撞位置播放碰撞音效
+    ///
+    /// # 参数
+    /// - `cue`: 碰撞音效（挡板、墙壁或砖块）
+    /// - `position`: 碰撞位置
+    /// - `speed`: 小球的速度大小，没有小球参与（例如激光击中、爆炸波及）时为 None，使用基础音高
+    ///
+    /// # 逻辑
+    /// 音效以空间音频播放，缩放后左右墙壁之间的距离为 1，左右耳分别在两侧墙壁上（见 [`update_listener`]），
+    /// 碰撞越靠近一侧墙壁，这一侧的声道越响；正在播放的碰撞音效达到 [`MAX_COLLISION_VOICES`] 时不再播放
+    pub fn play(&mut self, cue: SoundCue, position: Vec2, speed: Option<f32>) {
+        let Some(sounds) = &self.sounds else {
+            return;
+        };
+        if self.voices.iter().count() >= MAX_COLLISION_VOICES {
+            return;
+        }
+        let pitch = speed.map_or(1.0, |speed| collision_pitch(speed, self.config.ball_speed));
+        let width = (self.config.right_wall - self.config.left_wall).max(1.0);
+        self.commands.spawn((
+            AudioPlayer(sounds.cue(cue).clone()),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(self.audio_settings.sfx_volume()))
+                .with_speed(pitch)
+                .with_spatial(true)
+                .with_spatial_scale(SpatialScale::new_2d(1.0 / width)),
+            Transform::from_translation(position.extend(0.0)),
+            CollisionVoice,
+        ));
+    }
+}
+
+/// 小球碰到挡板时播放挡板音效
+pub fn play_paddle_hit(hit: On<BallHitPaddle>, mut audio: CollisionAudio) {
+    audio.play(SoundCue::PaddleHit, hit.position, Some(hit.speed));
+}
+
+/// 小球碰到墙壁时播放墙壁音效
+pub fn play_wall_hit(hit: On<BallHitWall>, mut audio: CollisionAudio) {
+    audio.play(SoundCue::WallHit, hit.position, Some(hit.speed));
+}
+
+/// 砖块受损时在砖块位置播放砖块受损音效
+pub fn play_brick_damaged(damaged: On<BrickDamaged>, mut audio: CollisionAudio) {
+    audio.play(SoundCue::BrickDamaged, damaged.position, damaged.speed);
+}
+
+/// 砖块被摧毁时在砖块位置播放砖块破碎音效
+pub fn play_brick_break(destroyed: On<BrickDestroyed>, mut audio: CollisionAudio) {
+    audio.play(SoundCue::BrickBreak, destroyed.position, destroyed.speed);
+}
+
+/// 更新空间音频听者系统（游戏配置加载或修改时运行）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `config`: 游戏配置
+/// - `camera_query`: 相机实体，作为听者
+///
+/// # 说明
+/// 相机位于场地中央，左右耳的间距为左右墙壁之间的距离
+pub fn update_listener(mut commands: Commands, config: Res<GameConfig>, camera_query: Query<Entity, With<Camera2d>>) {
+    for camera in &camera_query {
+        commands
+            .entity(camera)
+            .insert(SpatialListener::new(config.right_wall - config.left_wall));
+    }
+}
+
+/// 播放音效
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `sounds`: 音效资源
+/// - `audio_settings`: 音量设置
+/// - `cue`: 要播放的音效
+///
+/// # 说明
+/// 碰撞音效在这里不区分位置和速度，也不受数量限制，游戏中的碰撞由 [`CollisionAudio`] 播放
+pub fn play_cue(commands: &mut Commands, sounds: &GameSounds, audio_settings: &AudioSettings, cue: SoundCue) {
+    // 播放设置：播放完成后自动销毁播放器实体，音量为主音量 × 音效音量
+    commands.spawn((
+        AudioPlayer(sounds.cue(cue).clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(audio_settings.sfx_volume())),
+    ));
+}
+
+/// 创建在事件 `E` 触发时播放音效的观察者
+///
+/// # 参数
+/// - `cue`: 要播放的音效
+///
+/// # 说明
+/// 音效资源加载完成前触发的事件不播放音效
+pub fn play_on<E: Event>(
+    cue: SoundCue,
+) -> impl FnMut(On<E>, Commands, Option<Res<GameSounds>>, Res<AudioSettings>) {
+    move |_event, mut commands, sounds, audio_settings| {
+        if let Some(sounds) = sounds {
+            play_cue(&mut commands, &sounds, &audio_settings, cue);
+        }
+    }
+}
+
+/// 播放游戏结束音效系统（进入游戏结束状态时调用）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `sounds`: 音效资源（加载完成前不存在）
+/// - `audio_settings`: 音量设置
+pub fn play_game_over_sound(mut commands: Commands, sounds: Option<Res<GameSounds>>, audio_settings: Res<AudioSettings>) {
+    if let Some(sounds) = sounds {
+        play_cue(&mut commands, &sounds, &audio_settings, SoundCue::GameOver);
+    }
+}
+
+/// 切换背景音乐系统（游戏状态变化或音效加载完成时运行）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `state`: 当前游戏状态
+/// - `sounds`: 音效资源（加载完成前不存在）
+/// - `music_query`: 正在播放的背景音乐
+///
+/// # 逻辑
+/// 当前状态的背景音乐已经在播放时保持不变（例如在菜单类界面之间切换、重新开始关卡），
+/// 否则所有正在播放的背景音乐开始淡出，并以静音开始播放新的背景音乐，由 [`fade_music`] 淡入
+pub fn switch_music(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    sounds: Option<Res<GameSounds>>,
+    mut music_query: Query<&mut Music>,
+) {
+    let Some(sounds) = sounds else {
+        return;
+    };
+    let wanted = MusicTrack::for_state(*state.get());
+    let mut already_playing = false;
+    for mut music in &mut music_query {
+        if Some(music.track) == wanted && !music.fading_out {
+            already_playing = true;
+        } else {
+            music.fading_out = true;
+        }
+    }
+
+    if let Some(track) = wanted
+        && !already_playing
+    {
+        commands.spawn((
+            AudioPlayer(sounds.music(track).clone()),
+            PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+            Music {
+                track,
+                fade: 0.0,
+                fading_out: false,
+            },
+        ));
+    }
+}
+
+/// 背景音乐淡入淡出系统（每帧运行）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `time`: 真实时间（暂停时虚拟时间冻结，淡入淡出仍需继续）
+/// - `audio_settings`: 音量设置
+/// - `music_query`: 背景音乐和对应的播放器（开始播放后才有 AudioSink）
+///
+/// # 说明
+/// 每帧按淡入淡出进度和设置中的音乐音量更新播放器音量，淡出结束后销毁实体
+pub fn fade_music(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    audio_settings: Res<AudioSettings>,
+    mut music_query: Query<(Entity, &mut Music, Option<&mut AudioSink>)>,
+) {
+    let step = time.delta_secs() / MUSIC_CROSSFADE_SECS;
+    for (entity, mut music, sink) in &mut music_query {
+        let direction = if music.fading_out { -1.0 } else { 1.0 };
+        music.fade = (music.fade + direction * step).clamp(0.0, 1.0);
+        if music.fading_out && music.fade == 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if let Some(mut sink) = sink {
+            sink.set_volume(Volume::Linear(music.fade * audio_settings.music_volume()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pitch_follows_ball_speed_within_limits() {
+        assert_eq!(collision_pitch(400.0, 400.0), 1.0);
+        assert!(collision_pitch(600.0, 400.0) > 1.0);
+        assert!(collision_pitch(200.0, 400.0) < 1.0);
+        assert_eq!(collision_pitch(10_000.0, 400.0), MAX_COLLISION_PITCH);
+        assert_eq!(collision_pitch(0.0, 400.0), MIN_COLLISION_PITCH);
+    }
+
+    #[test]
+    fn rapid_collisions_are_capped() {
+        let mut app = App::new();
+        app.init_resource::<AudioSettings>()
+            .init_resource::<GameConfig>()
+            .insert_resource(GameSounds {
+                paddle_hit: Handle::default(),
+                wall_hit: Handle::default(),
+                brick_break: Handle::default(),
+                brick_damaged: Handle::default(),
+                life_lost: Handle::default(),
+                launch: Handle::default(),
+                victory: Handle::default(),
+                game_over: Handle::default(),
+                menu_music: Handle::default(),
+                gameplay_music: Handle::default(),
+            })
+            .add_observer(play_wall_hit)
+            .add_observer(play_brick_damaged);
+
+        // 与游戏中一样通过命令缓冲区触发同一步长内的多次碰撞，墙壁和砖块的碰撞共用上限
+        let mut commands = app.world_mut().commands();
+        for x in 0..10 {
+            let position = Vec2::new(x as f32 * 10.0, 300.0);
+            commands.trigger(BallHitWall {
+                side: crate::components::WallLocation::Top,
+                position,
+                speed: 400.0,
+            });
+            commands.trigger(BrickDamaged {
+                entity: Entity::PLACEHOLDER,
+                position,
+                kind: crate::components::BrickKind::Steel,
+                remaining: None,
+                speed: Some(400.0),
+            });
+        }
+        let world = app.world_mut();
+        world.flush();
+        let voices = world.query_filtered::<(), With<CollisionVoice>>().iter(world).count();
+        assert_eq!(voices, MAX_COLLISION_VOICES);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::config::*;
 
 // 存放组件，枚举等
@@ -35,7 +36,7 @@ pub struct Collider;
 pub struct Wall;
 
 /// 墙壁位置枚举，表示墙壁在游戏区域的哪一侧
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WallLocation {
     Left,   // 左侧
     Right,  // 右侧
@@ -45,40 +46,40 @@ pub enum WallLocation {
 
 impl WallLocation {
     /// 返回墙壁中心的位置，用于transform.translation()
-    /// 这是一个多行注释，说明了函数的功能和用途
-    pub fn position(&self) -> Vec2 {
+    ///
+    /// # 参数
+    /// - `config`: 游戏配置，提供四面墙壁的坐标
+    pub fn position(&self, config: &GameConfig) -> Vec2 {
         match self {
-            // 左墙位置，x坐标为LEFT_WALL常量-450，y坐标为0
-            WallLocation::Left => Vec2::new(LEFT_WALL, 0.),
-            // 右墙位置，x坐标为RIGHT_WALL常量，y坐标为0
-            WallLocation::Right => Vec2::new(RIGHT_WALL, 0.),
-            // 底墙位置，x坐标为0，y坐标为BOTTOM_WALL常量
-            WallLocation::Bottom => Vec2::new(0., BOTTOM_WALL),
-            // 顶墙位置，x坐标为0，y坐标为TOP_WALL常量
-            WallLocation::Top => Vec2::new(0., TOP_WALL),
+            // 左墙位置，x坐标为左边界，y坐标为0
+            WallLocation::Left => Vec2::new(config.left_wall, 0.),
+            // 右墙位置，x坐标为右边界，y坐标为0
+            WallLocation::Right => Vec2::new(config.right_wall, 0.),
+            // 底墙位置，x坐标为0，y坐标为下边界
+            WallLocation::Bottom => Vec2::new(0., config.bottom_wall),
+            // 顶墙位置，x坐标为0，y坐标为上边界
+            WallLocation::Top => Vec2::new(0., config.top_wall),
         }
     }
 
     /// 返回墙壁的(x, y)尺寸，用于transform.scale()
-    /// 这是一个多行注释，说明了函数的功能和用途
-    pub fn size(&self) -> Vec2 {
-        // 计算竞技场高度，即顶墙和底墙之间的距离
-        let arena_height = TOP_WALL - BOTTOM_WALL;
-        // 计算竞技场宽度，即右墙和左墙之间的距离
-        let arena_width = RIGHT_WALL - LEFT_WALL;
-        // 确保常量值正确，高度必须大于0
-        assert!(arena_height > 0.0);
-        // 确保常量值正确，宽度必须大于0
-        assert!(arena_width > 0.0);
+    ///
+    /// # 参数
+    /// - `config`: 游戏配置（竞技场宽高为正数的约束由 [`GameConfig::validate`] 保证）
+    pub fn size(&self, config: &GameConfig) -> Vec2 {
+        // 竞技场高度，即顶墙和底墙之间的距离
+        let arena_height = config.arena_height();
+        // 竞技场宽度，即右墙和左墙之间的距离
+        let arena_width = config.arena_width();
 
         match self {
             // 左墙和右墙的尺寸：宽度为墙壁厚度，高度为竞技场高度加上墙壁厚度
             WallLocation::Left | WallLocation::Right => {
-                Vec2::new(WALL_THICKNESS, arena_height + WALL_THICKNESS)
+                Vec2::new(config.wall_thickness, arena_height + config.wall_thickness)
             }
             // 底墙和顶墙的尺寸：宽度为竞技场宽度加上墙壁厚度，高度为墙壁厚度
             WallLocation::Bottom | WallLocation::Top => {
-                Vec2::new(arena_width + WALL_THICKNESS, WALL_THICKNESS)
+                Vec2::new(arena_width + config.wall_thickness, config.wall_thickness)
             }
         }
     }
@@ -87,24 +88,185 @@ impl WallLocation {
 impl Wall {
     /// 构建器方法，用于创建墙壁实体
     // 注意Sprite和Transform与Wall一起使用，覆盖必需组件的默认值
-    pub fn new(location: WallLocation) -> (Wall, WallLocation, Sprite, Transform) {
+    pub fn new(location: WallLocation, config: &GameConfig) -> (Wall, WallLocation, Sprite, Transform) {
         (
             Wall,                                    // 创建Wall组件实例
             location,                                // 创建WallLocation组件，用于识别墙壁位置
-            Sprite::from_color(WALL_COLOR, Vec2::ONE), // 创建带有指定颜色和默认大小的精灵组件
+            Sprite::from_color(config.wall_color, Vec2::ONE), // 创建带有指定颜色和默认大小的精灵组件
             Transform {                             // 创建变换组件，定义墙壁的位置和大小
                 // 将Vec2转换为Vec3，需要添加z坐标
                 // 用于确定精灵的渲染顺序
-                translation: location.position().extend(0.0), // 设置墙壁位置，从2D坐标扩展为3D坐标
+                translation: location.position(config).extend(0.0), // 设置墙壁位置，从2D坐标扩展为3D坐标
                 // 2D对象的z轴缩放必须始终为1.0，
                 // 否则它们的渲染顺序会受到影响
-                scale: location.size().extend(1.0),          // 设置墙壁大小，从2D尺寸扩展为3D尺寸
+                scale: location.size(config).extend(1.0),          // 设置墙壁大小，从2D尺寸扩展为3D尺寸
                 ..default()                                // 使用Transform组件的默认值设置其他属性
             },
         )
     }
 }
 
+/// 砖块类型组件
+/// 对应关卡网格中的一个字符，决定砖块的耐久、颜色和被摧毁时的效果
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum BrickKind {
+    /// 普通砖块，被击中一次即销毁（网格字符 `#`）
+    Normal,
+    /// 坚固砖块，需要击中多次才能销毁（网格字符 `2`-`9`，数字即所需击中次数）
+    Tough(u8),
+    /// 钢铁砖块，只反弹小球，永远不会被摧毁，不计入胜利条件（网格字符 `S`）
+    Steel,
+    /// 爆炸砖块，被摧毁时同时摧毁爆炸半径内的其他砖块（网格字符 `X`）
+    Explosive,
+}
+
+impl BrickKind {
+    /// 将关卡网格中的字符解析为砖块类型
+    ///
+    /// # 返回值
+    /// - `Ok(Some(kind))`：该格子有砖块
+    /// - `Ok(None)`：空格子（`.` 或空格）
+    /// - `Err(cell)`：无法识别的字符
+    pub fn from_cell(cell: char) -> Result<Option<BrickKind>, char> {
+        match cell {
+            '.' | ' ' => Ok(None),
+            '#' => Ok(Some(BrickKind::Normal)),
+            '2'..='9' => Ok(Some(BrickKind::Tough(cell as u8 - b'0'))),
+            'S' => Ok(Some(BrickKind::Steel)),
+            'X' => Ok(Some(BrickKind::Explosive)),
+            other => Err(other),
+        }
+    }
+
+    /// 砖块的初始耐久（需要击中的次数），钢铁砖块返回 None
+    pub fn hit_points(&self) -> Option<u8> {
+        match self {
+            BrickKind::Normal | BrickKind::Explosive => Some(1),
+            BrickKind::Tough(hit_points) => Some(*hit_points),
+            BrickKind::Steel => None,
+        }
+    }
+
+    /// 摧毁砖块的基础得分（乘以连击倍数后计入分数），坚固砖块每点耐久 10 分，钢铁砖块不得分
+    pub fn points(&self) -> usize {
+        match self {
+            BrickKind::Normal => 10,
+            BrickKind::Tough(hit_points) => 10 * *hit_points as usize,
+            BrickKind::Explosive => 20,
+            BrickKind::Steel => 0,
+        }
+    }
+
+    /// 砖块是否可以被摧毁，只有可摧毁的砖块计入胜利条件
+    pub fn is_destructible(&self) -> bool {
+        self.hit_points().is_some()
+    }
+
+    /// 根据剩余耐久计算砖块颜色
+    ///
+    /// # 参数
+    /// - `remaining`: 剩余耐久，坚固砖块耐久越高颜色越深
+    /// - `config`: 游戏配置，提供各类砖块的颜色
+    pub fn color(&self, remaining: u8, config: &GameConfig) -> Color {
+        match self {
+            BrickKind::Normal => config.brick_color,
+            BrickKind::Tough(hit_points) => {
+                // 剩余1点耐久时与普通砖块同色，满耐久时为最深的颜色
+                let t = (remaining.saturating_sub(1)) as f32 / (hit_points.saturating_sub(1)).max(1) as f32;
+                config.brick_color.mix(&config.tough_brick_color, t)
+            }
+            BrickKind::Steel => config.steel_brick_color,
+            BrickKind::Explosive => config.explosive_brick_color,
+        }
+    }
+}
+
+/// 砖块耐久组件，记录砖块还需要被击中几次才会被摧毁
+/// 钢铁砖块没有此组件
+#[derive(Component, Deref, DerefMut)]
+pub struct BrickHealth(pub u8);
+
+/// 道具类型
+/// 砖块被摧毁时有概率掉落道具胶囊，挡板接住后生效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum PowerUpKind {
+    /// 加宽挡板
+    WidePaddle,
+    /// 多球：分裂出额外的小球
+    MultiBall,
+    /// 减速小球
+    SlowBall,
+    /// 粘性挡板：小球落到挡板上时被粘住，需要重新发射
+    StickyPaddle,
+    /// 激光：按发射键从挡板两端发射激光
+    Laser,
+    /// 额外生命
+    ExtraLife,
+}
+
+impl PowerUpKind {
+    /// 砖块可能掉落的道具
+    pub const DROPPABLE: [PowerUpKind; 6] = [
+        PowerUpKind::WidePaddle,
+        PowerUpKind::MultiBall,
+        PowerUpKind::SlowBall,
+        PowerUpKind::StickyPaddle,
+        PowerUpKind::Laser,
+        PowerUpKind::ExtraLife,
+    ];
+
+    /// 道具效果持续时间（秒），立即生效的道具返回 None
+    pub fn duration(&self) -> Option<f32> {
+        match self {
+            PowerUpKind::WidePaddle => Some(WIDE_PADDLE_DURATION),
+            PowerUpKind::SlowBall => Some(SLOW_BALL_DURATION),
+            PowerUpKind::StickyPaddle => Some(STICKY_PADDLE_DURATION),
+            PowerUpKind::Laser => Some(LASER_DURATION),
+            PowerUpKind::MultiBall | PowerUpKind::ExtraLife => None,
+        }
+    }
+
+    /// 道具名称（中文原文，显示在道具状态栏时通过 [`Language::tr`](crate::Language::tr) 翻译）
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerUpKind::WidePaddle => "加宽",
+            PowerUpKind::MultiBall => "多球",
+            PowerUpKind::SlowBall => "减速",
+            PowerUpKind::StickyPaddle => "粘板",
+            PowerUpKind::Laser => "激光",
+            PowerUpKind::ExtraLife => "生命",
+        }
+    }
+
+    /// 道具胶囊上显示的字母
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::WidePaddle => "W",
+            PowerUpKind::MultiBall => "M",
+            PowerUpKind::SlowBall => "S",
+            PowerUpKind::StickyPaddle => "G",
+            PowerUpKind::Laser => "L",
+            PowerUpKind::ExtraLife => "+",
+        }
+    }
+
+    /// 道具胶囊颜色
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::WidePaddle => Color::srgb(0.3, 0.6, 1.0),
+            PowerUpKind::MultiBall => Color::srgb(0.9, 0.4, 0.9),
+            PowerUpKind::SlowBall => Color::srgb(0.3, 0.8, 0.4),
+            PowerUpKind::StickyPaddle => Color::srgb(0.9, 0.8, 0.2),
+            PowerUpKind::Laser => Color::srgb(1.0, 0.3, 0.3),
+            PowerUpKind::ExtraLife => BALL_COLOR,
+        }
+    }
+}
+
+/// 道具胶囊组件，胶囊带有 Velocity 组件，由 apply_velocity 系统负责下落
+#[derive(Component)]
+pub struct PowerUp(pub PowerUpKind);
+
 /// 碰撞方向枚举
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Collision {
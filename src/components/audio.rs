@@ -1,37 +1,133 @@
 use bevy::prelude::*;
 
+use crate::state::GameState;
+
+/// 音效
+/// 每种游戏事件对应一个音效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundCue {
+    /// 小球碰到挡板
+    PaddleHit,
+    /// 小球碰到墙壁
+    WallHit,
+    /// 砖块被摧毁
+    BrickBreak,
+    /// 砖块被击中但没有被摧毁（包括钢铁砖块）
+    BrickDamaged,
+    /// 失去一条生命
+    LifeLost,
+    /// 发射小球
+    Launch,
+    /// 通关
+    Victory,
+    /// 游戏结束
+    GameOver,
+}
+
+/// 背景音乐
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicTrack {
+    /// 菜单类界面（主菜单、关卡选择、设置）
+    Menu,
+    /// 游戏进行中
+    Gameplay,
+}
+
+impl MusicTrack {
+    /// 游戏状态对应的背景音乐，结算界面（游戏结束、胜利、战役成绩）不播放背景音乐
+    pub fn for_state(state: GameState) -> Option<Self> {
+        match state {
+            GameState::Menu | GameState::LevelSelect | GameState::Settings | GameState::Controls => {
+                Some(MusicTrack::Menu)
+            }
+            GameState::Playing => Some(MusicTrack::Gameplay),
+            GameState::GameOver | GameState::Victory | GameState::CampaignResults => None,
+        }
+    }
+}
+
 /// 游戏音效资源集合
-/// 包含游戏中所有使用的音效，便于统一管理和访问
+/// 包含游戏中所有使用的音效和背景音乐，便于统一管理和访问
 /// 实现了 Asset + FromWorld trait，支持通过 assets_tracking 模块异步加载
+///
+/// # 说明
+/// 所有句柄都标记为依赖，assets_tracking 等到全部音频文件加载完成后才插入这个资源
 #[derive(Resource, Asset, Clone, Reflect)]
 pub struct GameSounds {
-    /// 球碰撞音效（挡板、墙壁、砖块碰撞时播放）
-    pub collision: Handle<AudioSource>,
-    /// 游戏开始音效
-    pub game_start: Handle<AudioSource>,
-    /// 游戏结束音效
-    pub game_over: Handle<AudioSource>,
-    /// 得分音效（摧毁砖块时播放）
-    pub score: Handle<AudioSource>,
-    /// 胜利音效（清除所有砖块时播放）
+    /// 小球碰到挡板
+    #[dependency]
+    pub paddle_hit: Handle<AudioSource>,
+    /// 小球碰到墙壁
+    #[dependency]
+    pub wall_hit: Handle<AudioSource>,
+    /// 砖块被摧毁
+    #[dependency]
+    pub brick_break: Handle<AudioSource>,
+    /// 砖块被击中但没有被摧毁
+    #[dependency]
+    pub brick_damaged: Handle<AudioSource>,
+    /// 失去一条生命
+    #[dependency]
+    pub life_lost: Handle<AudioSource>,
+    /// 发射小球
+    #[dependency]
+    pub launch: Handle<AudioSource>,
+    /// 通关
+    #[dependency]
     pub victory: Handle<AudioSource>,
+    /// 游戏结束
+    #[dependency]
+    pub game_over: Handle<AudioSource>,
+    /// 菜单背景音乐
+    #[dependency]
+    pub menu_music: Handle<AudioSource>,
+    /// 游戏进行中的背景音乐
+    #[dependency]
+    pub gameplay_music: Handle<AudioSource>,
 }
 
 impl GameSounds {
-    /// 创建一个新的 GameSounds 实例，加载所有音效资源
+    /// 创建一个新的 GameSounds 实例，加载所有音效和背景音乐
     ///
     /// # 参数
-    /// - `asset_server`: 资源服务器，用于加载音效文件
+    /// - `asset_server`: 资源服务器，用于加载 `assets/sounds` 下的音频文件
     ///
     /// # 返回值
     /// 返回包含所有音效句柄的 GameSounds 实例
     pub fn new(asset_server: &AssetServer) -> Self {
         Self {
-            collision: asset_server.load("sounds/breakout_collision.ogg"),
-            game_start: asset_server.load("sounds/breakout_collision.ogg"),
-            game_over: asset_server.load("sounds/breakout_collision.ogg"),
-            score: asset_server.load("sounds/breakout_collision.ogg"),
-            victory: asset_server.load("sounds/breakout_collision.ogg"),
+            paddle_hit: asset_server.load("sounds/paddle_hit.ogg"),
+            wall_hit: asset_server.load("sounds/wall_hit.ogg"),
+            brick_break: asset_server.load("sounds/brick_break.ogg"),
+            brick_damaged: asset_server.load("sounds/brick_damaged.ogg"),
+            life_lost: asset_server.load("sounds/life_lost.ogg"),
+            launch: asset_server.load("sounds/launch.ogg"),
+            victory: asset_server.load("sounds/victory.ogg"),
+            game_over: asset_server.load("sounds/game_over.ogg"),
+            menu_music: asset_server.load("sounds/menu_music.ogg"),
+            gameplay_music: asset_server.load("sounds/gameplay_music.ogg"),
+        }
+    }
+
+    /// 音效对应的音频
+    pub fn cue(&self, cue: SoundCue) -> &Handle<AudioSource> {
+        match cue {
+            SoundCue::PaddleHit => &self.paddle_hit,
+            SoundCue::WallHit => &self.wall_hit,
+            SoundCue::BrickBreak => &self.brick_break,
+            SoundCue::BrickDamaged => &self.brick_damaged,
+            SoundCue::LifeLost => &self.life_lost,
+            SoundCue::Launch => &self.launch,
+            SoundCue::Victory => &self.victory,
+            SoundCue::GameOver => &self.game_over,
+        }
+    }
+
+    /// 背景音乐对应的音频
+    pub fn music(&self, track: MusicTrack) -> &Handle<AudioSource> {
+        match track {
+            MusicTrack::Menu => &self.menu_music,
+            MusicTrack::Gameplay => &self.gameplay_music,
         }
     }
 }
@@ -49,4 +145,4 @@ impl FromWorld for GameSounds {
         let asset_server = world.resource::<AssetServer>();
         Self::new(asset_server)
     }
-}
\ No newline at end of file
+}
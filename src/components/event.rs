@@ -1,5 +1,71 @@
+//! 游戏事件
+//! 碰撞检测只负责物理，碰撞的结果通过这些事件通知音效、计分等系统，各系统用观察者独立订阅
+
 use bevy::prelude::*;
 
-/// 碰撞事件，当球发生碰撞时触发
-#[derive(Event)]
-pub struct BallCollided;
\ No newline at end of file
+use crate::components::{BrickKind, WallLocation};
+
+/// 小球碰到挡板顶部时触发
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct BallHitPaddle {
+    /// 击中位置相对挡板中心的偏移，范围 [-1, 1]，-1 为最左侧
+    pub offset: f32,
+    /// 小球的速度大小
+    pub speed: f32,
+    /// 碰撞时小球的位置
+    pub position: Vec2,
+}
+
+/// 小球碰到墙壁时触发（底部墙壁除外，小球碰到底部墙壁会直接掉落）
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct BallHitWall {
+    /// 墙壁位置
+    pub side: WallLocation,
+    /// 碰撞时小球的位置
+    pub position: Vec2,
+    /// 小球的速度大小
+    pub speed: f32,
+}
+
+/// 砖块被小球或激光击中但没有被摧毁时触发（包括钢铁砖块）
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct BrickDamaged {
+    /// 砖块实体
+    pub entity: Entity,
+    /// 砖块中心位置
+    pub position: Vec2,
+    /// 砖块类型
+    pub kind: BrickKind,
+    /// 剩余耐久，钢铁砖块为 None
+    pub remaining: Option<u8>,
+    /// 击中砖块的小球的速度大小，激光击中时为 None
+    pub speed: Option<f32>,
+}
+
+/// 砖块被摧毁时触发，爆炸波及的每个砖块都会触发一次
+///
+/// # 说明
+/// 触发时砖块实体的销毁命令已经发出，观察者不应再访问 `entity` 上的组件
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct BrickDestroyed {
+    /// 砖块实体
+    pub entity: Entity,
+    /// 砖块中心位置
+    pub position: Vec2,
+    /// 砖块类型
+    pub kind: BrickKind,
+    /// 击中砖块的小球的速度大小，激光击中或爆炸波及时为 None
+    pub speed: Option<f32>,
+}
+
+/// 发射附着在挡板上的小球时触发
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BallLaunched;
+
+/// 场上最后一个小球掉落、失去一条生命时触发
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeLost;
+
+/// 所有可摧毁的砖块都被摧毁、通关时触发
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelCleared;
@@ -24,6 +24,16 @@ pub struct BallAttached;
 #[derive(Component)]
 pub struct Brick;
 
+/// 激光组件标记
+/// 激光道具生效时由挡板发射，击中砖块或顶部墙壁后消失
+#[derive(Component)]
+pub struct LaserBolt;
+
+/// 道具状态栏UI组件标记
+/// 用于在记分板下方显示当前生效的道具及剩余时间
+#[derive(Component)]
+pub struct PowerUpHudUi;
+
 /// 生命数显示UI组件标记
 /// 用于在屏幕右上角显示剩余小球数量
 #[derive(Component)]
@@ -39,9 +49,18 @@ pub struct LifeIndicator;
 #[derive(Component)]
 pub struct GameOverRoot;
 
-/// 返回菜单按钮组件标记
-#[derive(Component)]
-pub struct BackToMenuButton;
+/// 游戏结束、胜利和战役成绩界面的按钮组件，记录按钮对应的操作
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndScreenButton {
+    /// 重新挑战当前关卡
+    Retry,
+    /// 进入下一关
+    NextLevel,
+    /// 查看战役成绩（战役模式通关最后一关后）
+    CampaignResults,
+    /// 返回主菜单
+    BackToMenu,
+}
 
 /// 游戏实体根节点标记
 /// 用于标识游戏场景中的实体（挡板、小球、墙壁、砖块等），便于在退出游戏时清理
@@ -53,6 +72,27 @@ pub struct GamePlayRoot;
 #[derive(Component)]
 pub struct VictoryRoot;
 
+/// 战役成绩UI根节点标记
+/// 用于标识战役成绩界面的根实体，便于清理
+#[derive(Component)]
+pub struct CampaignResultsRoot;
+
+/// 暂停菜单UI根节点标记
+/// 用于标识暂停菜单的根实体，便于继续游戏时清理
+#[derive(Component)]
+pub struct PauseMenuRoot;
+
+/// 暂停菜单按钮组件，记录按钮对应的操作
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuButton {
+    /// 继续游戏
+    Resume,
+    /// 重新开始当前关卡
+    RestartLevel,
+    /// 返回主菜单
+    QuitToMenu,
+}
+
 /// 小球动画组件
 /// 用于控制精灵图集的帧动画
 #[derive(Component)]
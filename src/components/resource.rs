@@ -1,9 +1,87 @@
+use bevy::platform::collections::HashSet;
 use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::PowerUpKind;
+use crate::settings::Difficulty;
+use crate::config::{
+    BALL_SPEED_UP_PER_HIT, COMBO_HITS_PER_MULTIPLIER, LIFE_BONUS, MAX_COMBO_MULTIPLIER, TIME_BONUS_PAR_SECS, TIME_BONUS_PER_SEC,
+};
 
 /// 分数资源，跟踪游戏得分
 #[derive(Resource, Deref, DerefMut)]
 pub struct Score(pub usize);
 
+/// 连击资源，记录小球连续击中可摧毁砖块（中间没有碰到挡板）的次数
+///
+/// 砖块被击中或摧毁时加 1，小球碰到挡板、失去生命或开始新关卡时清零；
+/// 连击数决定砖块得分的倍数
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+pub struct Combo(pub usize);
+
+impl Combo {
+    /// 当前的得分倍数：每 [`COMBO_HITS_PER_MULTIPLIER`] 次连击加 1，最高 [`MAX_COMBO_MULTIPLIER`]
+    pub fn multiplier(&self) -> usize {
+        (1 + self.0 / COMBO_HITS_PER_MULTIPLIER).min(MAX_COMBO_MULTIPLIER)
+    }
+}
+
+/// 小球加速资源，记录本关（失去生命后重新计算）小球碰到挡板的次数
+///
+/// 小球每碰到一次挡板加速 [`BALL_SPEED_UP_PER_HIT`]，直到难度的加速上限
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+pub struct SpeedProgression(pub usize);
+
+impl SpeedProgression {
+    /// 当前小球速度相对初始速度的倍数
+    ///
+    /// # 参数
+    /// - `difficulty`: 本局的难度，决定加速上限
+    pub fn scale(&self, difficulty: Difficulty) -> f32 {
+        (1.0 + self.0 as f32 * BALL_SPEED_UP_PER_HIT).min(difficulty.max_speed_scale())
+    }
+}
+
+/// 关卡计时资源，记录本关已进行的游戏时间（暂停时不计时），用于计算通关用时奖励
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct LevelClock(pub Stopwatch);
+
+/// 通关奖励资源，记录最近一次通关获得的奖励分数，在胜利界面显示
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClearBonus {
+    /// 剩余生命奖励
+    pub lives: usize,
+    /// 通关用时奖励
+    pub time: usize,
+}
+
+impl ClearBonus {
+    /// 计算通关奖励
+    ///
+    /// # 参数
+    /// - `lives`: 通关时的剩余生命数
+    /// - `elapsed_secs`: 通关用时（秒）
+    ///
+    /// # 返回值
+    /// 每条剩余生命 [`LIFE_BONUS`] 分；用时比 [`TIME_BONUS_PAR_SECS`] 每少一秒（不足一秒不计）
+    /// [`TIME_BONUS_PER_SEC`] 分
+    pub fn new(lives: usize, elapsed_secs: f32) -> Self {
+        let seconds_left = (TIME_BONUS_PAR_SECS - elapsed_secs).max(0.0) as usize;
+        Self {
+            lives: lives * LIFE_BONUS,
+            time: seconds_left * TIME_BONUS_PER_SEC,
+        }
+    }
+
+    /// 奖励总分
+    pub fn total(&self) -> usize {
+        self.lives + self.time
+    }
+}
+
 /// 玩家生命数资源，跟踪剩余小球数量
 ///
 /// # 默认值
@@ -17,4 +95,138 @@ impl Default for Lives {
     }
 }
 
+/// 剩余可摧毁砖块数量资源
+///
+/// 生成关卡时设置，砖块被摧毁时立即减少（不必等待 despawn 命令生效），
+/// 归零即为通关。钢铁砖块不计入
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct RemainingBricks(pub usize);
+
+/// 关卡进度资源，记录每个关卡的通关情况
+///
+/// 关卡以其在关卡目录中的索引标识，每关的最高分见 [`HighScores`](crate::high_scores::HighScores)
+#[derive(Resource, Default, Debug)]
+pub struct LevelProgress {
+    /// 已通关的关卡
+    pub cleared: HashSet<usize>,
+}
+
+impl LevelProgress {
+    /// 关卡是否已解锁
+    /// 第一关始终解锁，之后的关卡需要通关前一关才能解锁
+    pub fn is_unlocked(&self, level: usize) -> bool {
+        level == 0 || self.cleared.contains(&(level - 1))
+    }
+
+    /// 记录通关的关卡
+    ///
+    /// # 参数
+    /// - `level`: 关卡索引
+    pub fn record_cleared(&mut self, level: usize) {
+        self.cleared.insert(level);
+    }
+}
+
+/// 游戏模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// 从关卡选择界面进入的单个关卡，通关后可以继续挑战下一关
+    #[default]
+    SingleLevel,
+    /// 战役模式：从第一关开始依次挑战所有关卡，全部通关后显示战役成绩
+    Campaign,
+}
+
+/// 一局游戏中通关的关卡
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClearedLevel {
+    /// 关卡索引
+    pub level: usize,
+    /// 关卡名称
+    pub name: String,
+    /// 这一关获得的分数
+    pub points: usize,
+}
+
+/// 本局游戏进度资源
+///
+/// 记录游戏模式、难度和本局已通关的关卡；进入下一关时分数累计，
+/// 重试关卡时分数恢复为进入这一关时的分数（即已通关关卡的得分之和）
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunProgress {
+    /// 游戏模式
+    pub mode: GameMode,
+    /// 已通关的关卡，按通关顺序排列
+    pub cleared: Vec<ClearedLevel>,
+    /// 本局的难度，在开始一局游戏前选择（旧存档中没有这一项，视为普通难度）
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+impl RunProgress {
+    /// 已通关关卡的得分之和
+    pub fn banked_score(&self) -> usize {
+        self.cleared.iter().map(|cleared| cleared.points).sum()
+    }
+
+    /// 记录通关的关卡
+    ///
+    /// # 参数
+    /// - `level`: 关卡索引
+    /// - `name`: 关卡名称
+    /// - `score`: 通关时的总分，超出已通关关卡得分的部分记为这一关的得分
+    pub fn record_cleared(&mut self, level: usize, name: String, score: usize) {
+        let points = score.saturating_sub(self.banked_score());
+        self.cleared.push(ClearedLevel { level, name, points });
+    }
+}
+
+/// 游戏随机数生成器资源
+/// 所有游戏逻辑中的随机数（如道具掉落）都从这里获取
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng(StdRng::from_os_rng())
+    }
+}
+
+/// 当前生效的道具资源
+/// 记录所有限时道具及其剩余时间，挡板宽度和小球速度每帧根据它重新计算，
+/// 因此道具到期后相关数值会自动恢复为配置中的默认值
+#[derive(Resource, Default, Debug)]
+pub struct ActivePowerUps {
+    /// 生效中的限时道具及其计时器，按获得的先后顺序排列
+    pub effects: Vec<(PowerUpKind, Timer)>,
+}
+
+impl ActivePowerUps {
+    /// 激活限时道具，已生效的道具会重新计时
+    pub fn activate(&mut self, kind: PowerUpKind, duration: f32) {
+        let timer = Timer::from_seconds(duration, TimerMode::Once);
+        if let Some((_, existing)) = self.effects.iter_mut().find(|(active, _)| *active == kind) {
+            *existing = timer;
+        } else {
+            self.effects.push((kind, timer));
+        }
+    }
+
+    /// 道具是否生效中
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.effects.iter().any(|(active, _)| *active == kind)
+    }
 
+    /// 推进所有计时器，并移除已到期的道具
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        for (_, timer) in &mut self.effects {
+            timer.tick(delta);
+        }
+        self.effects.retain(|(_, timer)| !timer.is_finished());
+    }
+
+    /// 清除所有道具效果
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+}
@@ -1,8 +1,14 @@
 //! 游戏配置文件
 //! 集中管理所有游戏常量，便于统一调整和后续扩展
 //! Bevy 0.18 版本适配
+//!
+//! 手感相关的常量是 [`GameConfig`] 的默认值，运行时以 `assets/config/game.config.ron` 中的配置为准
 
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::assets_tracking::LoadResource;
 
 // 这些常量定义在Transform单位中。
 // 使用默认的2D相机，它们与屏幕像素一一对应。
@@ -27,6 +33,8 @@ pub const BALL_DIAMETER: f32 = 30.0;
 pub const BALL_SPEED: f32 = 400.0;
 /// 球的初始移动方向
 pub const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, -0.5);
+/// 小球每次碰到挡板后增加的速度（相对初始速度的比例），上限由难度决定
+pub const BALL_SPEED_UP_PER_HIT: f32 = 0.03;
 
 // ==================== 墙壁配置 ====================
 
@@ -53,6 +61,59 @@ pub const GAP_BETWEEN_BRICKS: f32 = 5.0;
 pub const GAP_BETWEEN_BRICKS_AND_CEILING: f32 = 20.0;
 /// 砖块与侧边的间隙
 pub const GAP_BETWEEN_BRICKS_AND_SIDES: f32 = 20.0;
+/// 爆炸砖块的爆炸半径（按砖块中心之间的距离计算）
+///
+/// 默认砖块尺寸和间隙下，中心横向相距 105、纵向相距 35，因此覆盖同一行左右各 1 块、
+/// 同一列上下各 3 块，以及左右相邻列上下各 1 块（斜向第 2 行相距约 126，不在范围内）
+pub const EXPLOSION_RADIUS: f32 = 120.0;
+
+/// 每个固定时间步长内，单个小球最多处理的碰撞次数
+/// 超过次数后剩余的移动时间被丢弃，避免小球卡在缝隙中时无限循环
+pub const MAX_BALL_BOUNCES_PER_STEP: usize = 4;
+
+// ==================== 道具配置 ====================
+
+/// 砖块被摧毁时掉落道具的概率
+pub const POWER_UP_DROP_CHANCE: f64 = 0.2;
+/// 道具胶囊大小
+pub const POWER_UP_SIZE: Vec2 = Vec2::new(40.0, 18.0);
+/// 道具胶囊下落速度
+pub const POWER_UP_FALL_SPEED: f32 = 150.0;
+/// 多球道具每个小球额外分裂出的小球数量
+pub const MULTI_BALL_SPLIT: usize = 2;
+/// 多个小球之间的散开角度（弧度），用于多球道具分裂和同时发射附着的小球
+pub const MULTI_BALL_SPREAD: f32 = std::f32::consts::PI / 9.0;
+/// 加宽挡板道具的宽度倍数
+pub const WIDE_PADDLE_SCALE: f32 = 1.5;
+/// 加宽挡板道具持续时间（秒）
+pub const WIDE_PADDLE_DURATION: f32 = 12.0;
+/// 减速道具的速度倍数
+pub const SLOW_BALL_SCALE: f32 = 0.6;
+/// 减速道具持续时间（秒）
+pub const SLOW_BALL_DURATION: f32 = 10.0;
+/// 粘性挡板道具持续时间（秒）
+pub const STICKY_PADDLE_DURATION: f32 = 10.0;
+/// 激光道具持续时间（秒）
+pub const LASER_DURATION: f32 = 8.0;
+/// 激光大小
+pub const LASER_SIZE: Vec2 = Vec2::new(4.0, 16.0);
+/// 激光飞行速度
+pub const LASER_SPEED: f32 = 700.0;
+/// 激光颜色
+pub const LASER_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+
+// ==================== 计分配置 ====================
+
+/// 连击数每增加多少，砖块得分倍数加 1
+pub const COMBO_HITS_PER_MULTIPLIER: usize = 5;
+/// 砖块得分倍数上限
+pub const MAX_COMBO_MULTIPLIER: usize = 5;
+/// 通关时每条剩余生命的奖励分数
+pub const LIFE_BONUS: usize = 100;
+/// 通关用时奖励的基准时间（秒），用时少于基准时间的每一秒都有奖励
+pub const TIME_BONUS_PAR_SECS: f32 = 90.0;
+/// 通关用时比基准时间每少一秒的奖励分数
+pub const TIME_BONUS_PER_SEC: usize = 5;
 
 // ==================== UI配置 ====================
 
@@ -60,6 +121,14 @@ pub const GAP_BETWEEN_BRICKS_AND_SIDES: f32 = 20.0;
 pub const SCOREBOARD_FONT_SIZE: f32 = 33.0;
 /// 记分板文本内边距
 pub const SCOREBOARD_TEXT_PADDING: Val = Val::Px(5.0);
+/// 道具状态栏字体大小
+pub const POWER_UP_HUD_FONT_SIZE: f32 = 20.0;
+/// 道具状态栏距离屏幕顶部的距离（位于记分板下方）
+pub const POWER_UP_HUD_TOP: Val = Val::Px(45.0);
+/// 记分板后连击数和得分倍数的字体大小
+pub const COMBO_HUD_FONT_SIZE: f32 = 24.0;
+/// 连击数和得分倍数的颜色（橙色）
+pub const COMBO_HUD_COLOR: Color = Color::srgb(1.0, 0.5, 0.0);
 
 /// 生命指示器图标大小（与小球直径相同）
 pub const LIFE_INDICATOR_SIZE: f32 = 20.0;
@@ -80,9 +149,333 @@ pub const PADDLE_COLOR: Color = Color::srgb(0.3, 0.3, 0.7);
 pub const BALL_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
 /// 砖块颜色
 pub const BRICK_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
+/// 坚固砖块满耐久时的颜色（耐久越低越接近普通砖块颜色）
+pub const TOUGH_BRICK_COLOR: Color = Color::srgb(0.15, 0.15, 0.55);
+/// 钢铁砖块颜色
+pub const STEEL_BRICK_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+/// 爆炸砖块颜色
+pub const EXPLOSIVE_BRICK_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
 /// 墙壁颜色
 pub const WALL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
 /// 文本颜色
 pub const TEXT_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
 /// 分数颜色
-pub const SCORE_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
\ No newline at end of file
+pub const SCORE_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
+// ==================== 运行时配置 ====================
+
+/// 游戏配置文件路径（相对于 assets 目录）
+pub const GAME_CONFIG_PATH: &str = "config/game.config.ron";
+
+/// 游戏配置插件
+/// 注册配置文件加载器，通过 assets_tracking 异步加载配置文件（需要在 `assets_tracking::plugin` 之后添加），
+/// 并在配置文件被修改后重新插入 [`GameConfig`] 资源（热重载需要启用 `hot_reload` 特性）
+pub(crate) fn plugin(app: &mut App) {
+    app.init_asset::<GameConfig>();
+    app.register_asset_loader(GameConfigLoader);
+    app.load_resource_from_path::<GameConfig>(GAME_CONFIG_PATH);
+    app.add_systems(
+        Update,
+        (
+            reload_game_config,
+            sync_clear_color.run_if(resource_exists_and_changed::<GameConfig>),
+        )
+            .chain(),
+    );
+}
+
+/// 游戏配置资源
+/// 挡板、小球、墙壁、砖块的尺寸和速度，以及游戏场景的颜色
+///
+/// # 说明
+/// - 从 [`GAME_CONFIG_PATH`] 加载，文件中省略的字段使用本文件中同名常量作为默认值
+/// - 挡板和小球的速度立即生效；尺寸、墙壁和砖块布局在下一次进入游戏时生效
+/// - 道具和 UI 布局的参数仍然是常量
+#[derive(Resource, Asset, Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    /// 挡板大小（宽度和高度）
+    pub paddle_size: Vec2,
+    /// 挡板与底部之间的距离
+    pub gap_between_paddle_and_floor: f32,
+    /// 挡板移动速度（像素/秒）
+    pub paddle_speed: f32,
+    /// 挡板与墙壁的最小距离
+    pub paddle_padding: f32,
+    /// 球的直径
+    pub ball_diameter: f32,
+    /// 球的移动速度
+    pub ball_speed: f32,
+    /// 墙壁厚度
+    pub wall_thickness: f32,
+    /// X坐标左边界
+    pub left_wall: f32,
+    /// X坐标右边界
+    pub right_wall: f32,
+    /// Y坐标下边界
+    pub bottom_wall: f32,
+    /// Y坐标上边界
+    pub top_wall: f32,
+    /// 砖块大小
+    pub brick_size: Vec2,
+    /// 挡板与砖块之间的精确距离
+    pub gap_between_paddle_and_bricks: f32,
+    /// 砖块之间的间隙
+    pub gap_between_bricks: f32,
+    /// 砖块与天花板的间隙
+    pub gap_between_bricks_and_ceiling: f32,
+    /// 砖块与侧边的间隙
+    pub gap_between_bricks_and_sides: f32,
+    /// 爆炸砖块的爆炸半径
+    pub explosion_radius: f32,
+    /// 背景颜色
+    pub background_color: Color,
+    /// 挡板颜色
+    pub paddle_color: Color,
+    /// 球的颜色（生命指示器）
+    pub ball_color: Color,
+    /// 砖块颜色
+    pub brick_color: Color,
+    /// 坚固砖块满耐久时的颜色
+    pub tough_brick_color: Color,
+    /// 钢铁砖块颜色
+    pub steel_brick_color: Color,
+    /// 爆炸砖块颜色
+    pub explosive_brick_color: Color,
+    /// 墙壁颜色
+    pub wall_color: Color,
+    /// 文本颜色
+    pub text_color: Color,
+    /// 分数颜色
+    pub score_color: Color,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            paddle_size: PADDLE_SIZE,
+            gap_between_paddle_and_floor: GAP_BETWEEN_PADDLE_AND_FLOOR,
+            paddle_speed: PADDLE_SPEED,
+            paddle_padding: PADDLE_PADDING,
+            ball_diameter: BALL_DIAMETER,
+            ball_speed: BALL_SPEED,
+            wall_thickness: WALL_THICKNESS,
+            left_wall: LEFT_WALL,
+            right_wall: RIGHT_WALL,
+            bottom_wall: BOTTOM_WALL,
+            top_wall: TOP_WALL,
+            brick_size: BRICK_SIZE,
+            gap_between_paddle_and_bricks: GAP_BETWEEN_PADDLE_AND_BRICKS,
+            gap_between_bricks: GAP_BETWEEN_BRICKS,
+            gap_between_bricks_and_ceiling: GAP_BETWEEN_BRICKS_AND_CEILING,
+            gap_between_bricks_and_sides: GAP_BETWEEN_BRICKS_AND_SIDES,
+            explosion_radius: EXPLOSION_RADIUS,
+            background_color: BACKGROUND_COLOR,
+            paddle_color: PADDLE_COLOR,
+            ball_color: BALL_COLOR,
+            brick_color: BRICK_COLOR,
+            tough_brick_color: TOUGH_BRICK_COLOR,
+            steel_brick_color: STEEL_BRICK_COLOR,
+            explosive_brick_color: EXPLOSIVE_BRICK_COLOR,
+            wall_color: WALL_COLOR,
+            text_color: TEXT_COLOR,
+            score_color: SCORE_COLOR,
+        }
+    }
+}
+
+impl GameConfig {
+    /// 挡板中心的Y坐标
+    pub fn paddle_y(&self) -> f32 {
+        self.bottom_wall + self.gap_between_paddle_and_floor
+    }
+
+    /// 小球附着在挡板上时，球心比挡板中心高出的距离
+    pub fn attached_ball_height(&self) -> f32 {
+        self.paddle_size.y / 2.0 + self.ball_diameter / 2.0 + 5.0
+    }
+
+    /// 竞技场宽度，即右墙和左墙之间的距离
+    pub fn arena_width(&self) -> f32 {
+        self.right_wall - self.left_wall
+    }
+
+    /// 竞技场高度，即顶墙和底墙之间的距离
+    pub fn arena_height(&self) -> f32 {
+        self.top_wall - self.bottom_wall
+    }
+
+    /// 砖块区域的宽度（去掉两侧的间隙）
+    pub fn bricks_area_width(&self) -> f32 {
+        self.arena_width() - 2. * self.gap_between_bricks_and_sides
+    }
+
+    /// 砖块区域底部的Y坐标
+    pub fn bricks_area_bottom(&self) -> f32 {
+        self.paddle_y() + self.gap_between_paddle_and_bricks
+    }
+
+    /// 砖块区域的高度（从砖块区域底部到天花板间隙）
+    pub fn bricks_area_height(&self) -> f32 {
+        self.top_wall - self.bricks_area_bottom() - self.gap_between_bricks_and_ceiling
+    }
+
+    /// 校验配置
+    ///
+    /// # 说明
+    /// 尺寸和速度必须为正数，竞技场和砖块区域必须有可用空间
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = [
+            ("paddle_size.x", self.paddle_size.x),
+            ("paddle_size.y", self.paddle_size.y),
+            ("paddle_speed", self.paddle_speed),
+            ("ball_diameter", self.ball_diameter),
+            ("ball_speed", self.ball_speed),
+            ("wall_thickness", self.wall_thickness),
+            ("brick_size.x", self.brick_size.x),
+            ("brick_size.y", self.brick_size.y),
+            ("arena_width", self.arena_width()),
+            ("arena_height", self.arena_height()),
+            ("bricks_area_width", self.bricks_area_width()),
+            ("bricks_area_height", self.bricks_area_height()),
+        ];
+        for (name, value) in positive {
+            // 同时排除 NaN
+            if value.is_nan() || value <= 0.0 {
+                return Err(format!("{name} 必须大于 0，当前为 {value}"));
+            }
+        }
+
+        let non_negative = [
+            ("gap_between_paddle_and_floor", self.gap_between_paddle_and_floor),
+            ("paddle_padding", self.paddle_padding),
+            ("gap_between_paddle_and_bricks", self.gap_between_paddle_and_bricks),
+            ("gap_between_bricks", self.gap_between_bricks),
+            ("gap_between_bricks_and_ceiling", self.gap_between_bricks_and_ceiling),
+            ("gap_between_bricks_and_sides", self.gap_between_bricks_and_sides),
+            ("explosion_radius", self.explosion_radius),
+        ];
+        for (name, value) in non_negative {
+            if value.is_nan() || value < 0.0 {
+                return Err(format!("{name} 不能为负数，当前为 {value}"));
+            }
+        }
+
+        // 挡板必须能放进竞技场
+        let paddle_room = self.arena_width() - self.wall_thickness - 2. * self.paddle_padding;
+        if self.paddle_size.x > paddle_room {
+            return Err(format!(
+                "paddle_size.x ({}) 超过了竞技场的可用宽度 ({paddle_room})",
+                self.paddle_size.x
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 游戏配置文件加载器
+/// 解析 RON 文件并校验配置，校验失败时加载失败（热重载时保留原来的配置）
+#[derive(Default, TypePath)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let config: GameConfig = ron::de::from_bytes(&bytes)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+/// 配置热重载系统
+/// 配置文件被修改并重新加载成功后，用新的配置替换 [`GameConfig`] 资源
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `events`: 配置资产事件
+/// - `configs`: 已加载的配置资产
+fn reload_game_config(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && let Some(config) = configs.get(*id)
+        {
+            info!("游戏配置已重新加载");
+            commands.insert_resource(config.clone());
+        }
+    }
+}
+
+/// 根据配置更新背景颜色
+///
+/// # 参数
+/// - `config`: 游戏配置
+/// - `clear_color`: 背景颜色资源
+fn sync_clear_color(config: Res<GameConfig>, mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = config.background_color;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(GameConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn config_file_matches_defaults() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", "config/game.config.ron");
+        let bytes = std::fs::read(path).unwrap();
+        let config: GameConfig = ron::de::from_bytes(&bytes).unwrap();
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config, GameConfig::default());
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let config: GameConfig = ron::de::from_str("(ball_speed: 600.0)").unwrap();
+        assert_eq!(config.ball_speed, 600.0);
+        assert_eq!(config.paddle_size, PADDLE_SIZE);
+    }
+
+    #[test]
+    fn invalid_arena_is_rejected() {
+        let config = GameConfig {
+            left_wall: 100.0,
+            right_wall: -100.0,
+            ..default()
+        };
+        assert!(config.validate().is_err());
+
+        // 砖块区域被挤没了
+        let config = GameConfig {
+            gap_between_paddle_and_bricks: 1000.0,
+            ..default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = GameConfig {
+            ball_speed: f32::NAN,
+            ..default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
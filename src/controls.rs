@@ -0,0 +1,259 @@
+//! 按键设置模块
+//! 从设置界面进入的按键设置界面，列出每个动作绑定的按键，点击后按下新按键即可重新绑定
+//!
+//! 修改后的按键绑定由 [`SettingsPlugin`](crate::SettingsPlugin) 自动保存到设置文件
+
+use bevy::prelude::*;
+use crate::actions::{Action, KeyBindings};
+use crate::i18n::Language;
+use crate::state::GameState;
+use crate::ui_focus::{MenuInput, UiFocusSystems};
+use crate::widgets::{self, ButtonColors, ButtonLabel, UiTheme};
+
+/// 动作名称列宽度
+pub const ACTION_LABEL_WIDTH: f32 = 200.0;
+/// 等待按键时按钮的颜色
+pub const CAPTURING_BUTTON_COLOR: Color = Color::srgb(0.8, 0.5, 0.2);
+
+/// 按键设置插件
+/// 注册按键设置界面的显示、交互、按键捕获和清理系统
+///
+/// # 说明
+/// 按键绑定资源 [`KeyBindings`] 由 [`GameplayPlugin`](crate::game::GameplayPlugin) 初始化
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RebindTarget>()
+            .add_systems(OnEnter(GameState::Controls), setup_controls)
+            .add_systems(
+                Update,
+                (handle_controls_input, capture_rebind_key, update_binding_labels)
+                    .run_if(in_state(GameState::Controls))
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::Controls), cleanup_controls)
+            // 等待新按键时暂停按钮焦点导航，方向键和确认键会被绑定到动作上
+            .configure_sets(
+                PreUpdate,
+                UiFocusSystems.run_if(|rebind_target: Res<RebindTarget>| rebind_target.0.is_none()),
+            );
+    }
+}
+
+/// 按键设置界面根节点标记组件
+#[derive(Component)]
+pub struct ControlsRoot;
+
+/// 重新绑定按钮，保存对应的动作
+#[derive(Component)]
+pub struct RebindButton(pub Action);
+
+/// 按键设置界面底部按钮的操作类型
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlsButton {
+    /// 恢复默认按键
+    ResetDefaults,
+    /// 返回设置界面
+    Back,
+}
+
+/// 正在等待新按键的动作资源，没有等待时为 None
+#[derive(Resource, Debug, Default)]
+pub struct RebindTarget(pub Option<Action>);
+
+/// 初始化按键设置界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于创建UI实体
+/// - `theme`: 界面主题
+/// - `bindings`: 当前的按键绑定
+/// - `rebind_target`: 正在等待新按键的动作，进入界面时清空
+/// - `language`: 界面语言
+///
+/// # 说明
+/// 每个动作一行：左侧为动作名称，右侧为显示当前按键的按钮；底部为恢复默认和返回按钮
+pub fn setup_controls(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    bindings: Res<KeyBindings>,
+    mut rebind_target: ResMut<RebindTarget>,
+    language: Res<Language>,
+) {
+    rebind_target.0 = None;
+
+    commands
+        .spawn((widgets::screen_with_gap(theme.background, 15.0), ControlsRoot))
+        .with_children(|parent| {
+            parent.spawn(widgets::title(&theme, language.tr("按键设置"), theme.title_color));
+
+            for action in Action::ALL {
+                parent.spawn(widgets::row(20.0)).with_children(|row| {
+                    row.spawn((
+                        widgets::label(&theme, language.tr(action.label()), theme.text_font_size, theme.title_color),
+                        Node {
+                            width: Val::Px(ACTION_LABEL_WIDTH),
+                            ..default()
+                        },
+                    ));
+                    row.spawn((
+                        widgets::button(
+                            &theme,
+                            bindings.label(action, *language),
+                            theme.menu_button,
+                            theme.small_button,
+                        ),
+                        RebindButton(action),
+                    ));
+                });
+            }
+
+            parent
+                .spawn(Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..widgets::row(30.0)
+                })
+                .with_children(|bar| {
+                    for (label, button) in [
+                        ("恢复默认", ControlsButton::ResetDefaults),
+                        ("返回", ControlsButton::Back),
+                    ] {
+                        bar.spawn((
+                            widgets::button(&theme, language.tr(label), theme.menu_button, theme.button),
+                            button,
+                        ));
+                    }
+                });
+        });
+}
+
+/// 处理按键设置界面按钮交互系统
+///
+/// # 参数
+/// - `rebind_query`: 查询重新绑定按钮的交互状态
+/// - `button_query`: 查询底部按钮的交互状态
+/// - `rebind_target`: 正在等待新按键的动作
+/// - `bindings`: 按键绑定
+/// - `next_state`: 下一个游戏状态
+///
+/// # 功能
+/// - 点击重新绑定按钮后等待新按键，再次点击取消等待
+/// - 点击恢复默认时所有动作恢复默认按键
+/// - 点击返回时回到设置界面
+pub fn handle_controls_input(
+    rebind_query: Query<(&Interaction, &RebindButton), Changed<Interaction>>,
+    button_query: Query<(&Interaction, &ControlsButton), Changed<Interaction>>,
+    mut rebind_target: ResMut<RebindTarget>,
+    mut bindings: ResMut<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in &rebind_query {
+        if *interaction == Interaction::Pressed {
+            rebind_target.0 = if rebind_target.0 == Some(button.0) { None } else { Some(button.0) };
+        }
+    }
+
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        rebind_target.0 = None;
+        match button {
+            ControlsButton::ResetDefaults => *bindings = KeyBindings::default(),
+            ControlsButton::Back => next_state.set(GameState::Settings),
+        }
+    }
+}
+
+/// 捕获新按键系统
+///
+/// # 参数
+/// - `keyboard_input`: 键盘输入资源
+/// - `menu_input`: 界面操作资源
+/// - `rebind_target`: 正在等待新按键的动作
+/// - `bindings`: 按键绑定
+/// - `next_state`: 下一个游戏状态
+///
+/// # 说明
+/// 等待新按键时，下一个按下的按键（包括 Esc）绑定到该动作；
+/// 用确认键按下重新绑定按钮的那一帧不捕获，避免把确认键本身绑定到动作上；
+/// 没有等待时，按下返回键或手柄 B 键回到设置界面
+pub fn capture_rebind_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    menu_input: Res<MenuInput>,
+    mut rebind_target: ResMut<RebindTarget>,
+    mut bindings: ResMut<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(action) = rebind_target.0 else {
+        if menu_input.back {
+            next_state.set(GameState::Settings);
+        }
+        return;
+    };
+    if rebind_target.is_changed() {
+        return;
+    }
+    if let Some(key) = keyboard_input.get_just_pressed().next() {
+        bindings.rebind(action, *key);
+        rebind_target.0 = None;
+    }
+}
+
+/// 更新重新绑定按钮文本和颜色系统
+///
+/// # 参数
+/// - `bindings`: 按键绑定
+/// - `rebind_target`: 正在等待新按键的动作
+/// - `theme`: 界面主题
+/// - `button_query`: 查询重新绑定按钮的动作和颜色
+/// - `label_query`: 查询按钮文字及其所属的按钮
+/// - `language`: 界面语言
+///
+/// # 说明
+/// 等待新按键的按钮显示提示文字并使用醒目的颜色，其他按钮显示当前按键
+pub fn update_binding_labels(
+    bindings: Res<KeyBindings>,
+    rebind_target: Res<RebindTarget>,
+    theme: Res<UiTheme>,
+    mut button_query: Query<(&RebindButton, &mut ButtonColors)>,
+    mut label_query: Query<(&ChildOf, &mut Text), With<ButtonLabel>>,
+    language: Res<Language>,
+) {
+    if !bindings.is_changed() && !rebind_target.is_changed() {
+        return;
+    }
+    for (child_of, mut text) in &mut label_query {
+        let Ok((button, mut colors)) = button_query.get_mut(child_of.parent()) else {
+            continue;
+        };
+        let capturing = rebind_target.0 == Some(button.0);
+        colors.set_if_neq(if capturing {
+            ButtonColors::solid(CAPTURING_BUTTON_COLOR)
+        } else {
+            theme.menu_button
+        });
+        text.0 = if capturing {
+            language.tr("请按下新按键...").to_string()
+        } else {
+            bindings.label(button.0, *language)
+        };
+    }
+}
+
+/// 清理按键设置界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `root_query`: 按键设置界面根节点
+/// - `rebind_target`: 正在等待新按键的动作，离开界面时清空
+pub fn cleanup_controls(
+    mut commands: Commands,
+    root_query: Query<Entity, With<ControlsRoot>>,
+    mut rebind_target: ResMut<RebindTarget>,
+) {
+    rebind_target.0 = None;
+    for entity in &root_query {
+        commands.entity(entity).despawn();
+    }
+}
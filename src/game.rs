@@ -5,4 +5,175 @@ mod game_over;
 pub use game_over::*;
 
 mod victory;
-pub use victory::*;
\ No newline at end of file
+pub use victory::*;
+
+mod power_up;
+pub use power_up::*;
+
+mod pause;
+pub use pause::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod input;
+pub use input::*;
+
+mod replay;
+pub use replay::*;
+
+mod run;
+pub use run::*;
+
+mod campaign_results;
+pub use campaign_results::*;
+
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use crate::actions::KeyBindings;
+use crate::components::*;
+use crate::config::GameConfig;
+use crate::i18n::Language;
+use crate::settings::Difficulty;
+use crate::state::{GameState, Level, PauseState, PlayingState};
+
+/// 游戏玩法插件
+/// 注册游戏状态、游戏进行中需要的资源和所有系统（挡板、小球、碰撞、道具、记分板、暂停）
+///
+/// # 说明
+/// 插件本身不依赖窗口、渲染和音频，只需要以下插件即可运行：
+/// - `MinimalPlugins`：时间和固定时间步长
+/// - `StatesPlugin`：状态切换
+/// - `InputPlugin`：键盘输入
+/// - `AssetPlugin`：加载小球精灵图（还需要注册 `Image` 和 `TextureAtlasLayout` 资产类型）
+/// - [`WidgetPlugin`](crate::WidgetPlugin)：界面主题，道具状态栏使用主题字体（还需要注册 `Font` 资产类型）
+///
+/// 没有 `TextPlugin` 时需要手动初始化 `TextIterScratch` 资源（记分板更新文本时使用）。
+///
+/// 游戏中的系统只在 [`PauseState::Running`] 时运行；暂停菜单界面由 [`PausePlugin`] 显示。
+///
+/// 游戏逻辑全部在 `FixedUpdate` 中运行，玩家输入每个固定时间步长采样一次（见 [`PlayerInput`]），
+/// 每局游戏的输入由 [`ReplayRecorder`] 录制，插入 [`ReplayPlayback`] 后进入游戏即可回放。
+///
+/// 碰撞音效不在此插件中，由 [`AudioPlugin`](crate::audio::AudioPlugin) 播放
+pub struct GameplayPlugin;
+
+impl Plugin for GameplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // 初始化游戏状态
+            .init_state::<GameState>()
+            .init_state::<PlayingState>()
+            .add_sub_state::<PauseState>()
+            // 无窗口运行时没有 WindowPlugin，需要手动注册窗口焦点消息
+            .add_message::<WindowFocused>()
+            .insert_resource(Score(0))  // 初始化分数资源为0
+            .insert_resource(Lives::default())  // 初始化生命数资源为默认值3
+            .insert_resource(Level::default())  // 初始化关卡资源为默认值（第一关）
+            .init_resource::<RemainingBricks>()  // 初始化剩余砖块数量资源
+            .init_resource::<ActivePowerUps>()  // 初始化道具效果资源
+            .init_resource::<GameRng>()  // 初始化随机数生成器
+            .init_resource::<GameConfig>()  // 默认游戏配置（config::plugin 加载配置文件后会覆盖它）
+            .init_resource::<Difficulty>()  // 默认难度（SettingsPlugin 读取设置文件后会覆盖它）
+            .init_resource::<Language>()  // 默认界面语言（SettingsPlugin 读取设置文件后会覆盖它）
+            .init_resource::<PlayerInput>()  // 初始化玩家输入资源
+            .init_resource::<FireLatch>()  // 初始化发射键锁存资源
+            .init_resource::<PointerTarget>()  // 初始化指针位置资源
+            .init_resource::<InputSettings>()  // 默认输入设置（SettingsPlugin 读取设置文件后会覆盖它）
+            .init_resource::<KeyBindings>()  // 默认按键绑定（SettingsPlugin 读取设置文件后会覆盖它）
+            .init_resource::<ReplayRecorder>()  // 初始化录像记录器
+            .init_resource::<RunProgress>()  // 初始化本局游戏进度（游戏模式和已通关关卡）
+            .init_resource::<Combo>()  // 初始化连击资源
+            .init_resource::<LevelClock>()  // 初始化关卡计时
+            .init_resource::<ClearBonus>()  // 初始化通关奖励资源
+            .init_resource::<SpeedProgression>()  // 初始化小球加速资源
+
+            // 回到菜单时重置分数和生命数，为下一局做准备
+            .add_systems(OnEnter(GameState::Menu), reset_game_state)
+            // 继续游戏时，先按关卡网格生成场景，再按快照重建
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (
+                    (setup_game, clear_power_ups, reset_level_scoring),
+                    // 录像从关卡生成后开始，回放时使用录像中的随机数种子
+                    start_recording,
+                    restore_run.run_if(resource_exists::<PendingResume>),
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_game_play)
+            // 回放结束后仍停留在结束界面，离开结束界面或回到菜单时才结束回放，
+            // 在此之前各插件通过回放资源判断这局不应写入存档、排行榜和关卡进度
+            .add_systems(OnExit(GameState::GameOver), stop_playback)
+            .add_systems(OnExit(GameState::Victory), stop_playback)
+            .add_systems(OnEnter(GameState::Menu), stop_playback)
+            .add_systems(
+                OnEnter(GameState::Victory),
+                record_cleared_level.run_if(not(resource_exists::<ReplayPlayback>)),
+            )
+            // 碰撞产生的事件：摧毁砖块时按连击倍数加分，小球碰到挡板或失去生命时连击中断，通关时加奖励分
+            .add_observer(award_brick_points)
+            .add_observer(extend_combo)
+            .add_observer(break_combo::<BallHitPaddle>)
+            .add_observer(break_combo::<LifeLost>)
+            .add_observer(award_clear_bonus)
+            // 小球每碰到一次挡板加速，失去生命后恢复初始速度
+            .add_observer(speed_up_ball)
+            .add_observer(reset_ball_speed)
+            .add_systems(OnEnter(PlayingState::ball_attached), (reset_ball, clear_power_ups))
+
+            // 暂停：Esc/P 切换，窗口失去焦点时自动暂停，暂停期间冻结虚拟时间
+            .add_systems(Update, toggle_pause.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, pause_on_focus_loss.run_if(in_state(PauseState::Running)))
+            .add_systems(OnEnter(PauseState::Paused), freeze_time)
+            .add_systems(OnExit(PauseState::Paused), unfreeze_time)
+
+            // 每帧运行的系统：发射键锁存（输入更新后立即锁存，下一个固定时间步长取走）、指针位置和画面更新
+            .add_systems(
+                PreUpdate,
+                (latch_fire_input, track_pointer)
+                    .after(InputSystems)
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_hint_visibility,
+                    animate_ball_sprite,
+                    update_scoreboard,
+                    update_lives_ui,
+                    update_power_up_hud,
+                )
+                    .run_if(in_state(PauseState::Running)),
+            )
+
+            // 固定时间步长系统
+            // 挡板、小球、道具和碰撞全部在固定时间步长中运行，只读取本步长采样的玩家输入，
+            // 因此相同的输入序列总能得到相同的结果（录像回放依赖这一点）
+            // 小球的移动和碰撞在同一个系统中完成（扫掠检测），需要在挡板移动之后运行
+            .add_systems(FixedFirst, apply_state_transitions)
+            .add_systems(
+                FixedUpdate,
+                (
+                    sample_player_input,
+                    move_paddle,
+                    // 粘性挡板会在发射后重新粘住小球，因此两种状态下都需要运行
+                    move_attached_ball,
+                    handle_ball_launch,
+                    tick_power_ups,
+                    tick_level_clock,
+                    collect_power_ups,
+                    update_paddle_size,
+                    fire_lasers,
+                    laser_collisions,
+                    apply_velocity,
+                    (sync_ball_speed, check_for_collisions)
+                        .chain()
+                        .run_if(in_state(PlayingState::ball_launched)),
+                )
+                    .run_if(in_state(PauseState::Running))
+                    .chain(),
+            );
+    }
+}
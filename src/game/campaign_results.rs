@@ -0,0 +1,104 @@
+// 战役成绩界面模块
+
+use bevy::prelude::*;
+use crate::state::GameState;
+use crate::components::{CampaignResultsRoot, EndScreenButton, RunProgress};
+use crate::i18n::Language;
+use crate::widgets::{self, UiTheme};
+
+/// 战役成绩标题颜色（金色）
+const RESULTS_TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+/// 每关得分的字体大小
+const LEVEL_RESULT_FONT_SIZE: f32 = 28.0;
+/// 总分字体大小
+const TOTAL_SCORE_FONT_SIZE: f32 = 40.0;
+
+/// 战役成绩插件
+/// 注册战役成绩界面的显示、交互和清理系统
+pub struct CampaignResultsPlugin;
+
+impl Plugin for CampaignResultsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::CampaignResults), setup_campaign_results)
+            .add_systems(
+                Update,
+                handle_campaign_results_input.run_if(in_state(GameState::CampaignResults)),
+            )
+            .add_systems(OnExit(GameState::CampaignResults), cleanup_campaign_results);
+    }
+}
+
+/// 设置战役成绩界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于创建UI实体
+/// - `theme`: 界面主题
+/// - `run`: 本局游戏进度，包含每关的得分
+/// - `language`: 界面语言
+///
+/// # 功能
+/// 创建战役成绩界面，包含标题、每关得分、总分和返回菜单按钮
+pub fn setup_campaign_results(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    run: Res<RunProgress>,
+    language: Res<Language>,
+) {
+    commands
+        .spawn((widgets::screen(theme.overlay), CampaignResultsRoot))
+        .with_children(|parent| {
+            parent.spawn(widgets::title(&theme, language.tr("战役完成"), RESULTS_TITLE_COLOR));
+            for cleared in &run.cleared {
+                parent.spawn(widgets::label(
+                    &theme,
+                    format!("{}: {}", cleared.name, cleared.points),
+                    LEVEL_RESULT_FONT_SIZE,
+                    Color::WHITE,
+                ));
+            }
+            parent.spawn(widgets::label(
+                &theme,
+                format!("{}: {}", language.tr("总分"), run.banked_score()),
+                TOTAL_SCORE_FONT_SIZE,
+                Color::WHITE,
+            ));
+            parent.spawn((
+                widgets::button(&theme, language.tr("返回菜单"), theme.overlay_button, theme.button),
+                EndScreenButton::BackToMenu,
+            ));
+        });
+}
+
+/// 处理战役成绩界面按钮点击系统
+///
+/// # 参数
+/// - `interaction_query`: 查询返回菜单按钮的交互状态
+/// - `next_state`: 下一个游戏状态资源，用于切换状态
+///
+/// # 功能
+/// 点击时返回主菜单状态（进入菜单时重置本局游戏进度）
+pub fn handle_campaign_results_input(
+    interaction_query: Query<(&Interaction, &EndScreenButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if interaction_query
+        .iter()
+        .any(|(interaction, action)| *interaction == Interaction::Pressed && *action == EndScreenButton::BackToMenu)
+    {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// 清理战役成绩界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `results_entities`: 查询所有带有 CampaignResultsRoot 标记的实体
+pub fn cleanup_campaign_results(
+    mut commands: Commands,
+    results_entities: Query<Entity, With<CampaignResultsRoot>>,
+) {
+    for entity in &results_entities {
+        commands.entity(entity).despawn();
+    }
+}
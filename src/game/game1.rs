@@ -1,49 +1,76 @@
 // 游戏的主体部分
 
+use bevy::ecs::entity::EntityHashSet;
 use bevy::prelude::*;
+use bevy::state::state::StateTransition;
+use crate::actions::KeyBindings;
 use crate::config::*;
+use crate::i18n::Language;
 use crate::components::*;
+use crate::levels::LevelCatalog;
+use super::input::{InputSettings, PlayerInput};
+use super::power_up::{spawn_power_up_hud, try_drop_power_up};
 use crate::state::{GameState, Level, PlayingState};
+use crate::widgets::UiTheme;
 //Aabb2d 二维轴对齐包围盒。 用来包裹一个复杂形状（角色模型）的最小矩形。通常用于碰撞检测的第一阶段（粗略检测），先快速排除掉明显不相交的物体。
 //BoundingCircle 包围圆。定义一个中心点和半径，任何距离中心点小于半径的点都被认为在物体内。
 //BoundingVolume (Trait) 这是一个抽象接口（Trait）。它定义了作为一个"包围体"必须具备的共同行为。
 //IntersectsVolume (Trait) 抽象接口（Trait），专门用于定义"检测碰撞"的行为。
 use bevy::{math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume},};
 
+/// 碰撞器查询：实体ID、位置、砖块类型（非砖块为None）、墙壁位置（非墙壁为None）
+pub type ColliderQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Transform, Option<&'static BrickKind>, Option<&'static WallLocation>),
+    // 小球的 Transform 由碰撞系统修改，排除小球以满足借用规则
+    (With<Collider>, Without<Ball>),
+>;
+
 /// 设置游戏场景系统（游戏状态进入时调用）
 ///
 /// # 参数
 /// - `commands`: 命令缓冲区，用于创建和修改实体
-/// - `meshes`: 网格资源集合，用于创建2D网格
-/// - `materials`: 材质资源集合，用于创建材质
 /// - `asset_server`: 资源服务器，用于加载游戏资源
+/// - `atlas_layouts`: 纹理图集布局资源，用于小球动画
 /// - `lives`: 生命数资源，用于显示剩余小球数量
-/// - `current_level`: 当前关卡资源，决定生成多少行砖块
+/// - `current_level`: 当前关卡资源，决定使用关卡目录中的哪个关卡
+/// - `level_catalog`: 关卡目录资源，提供砖块布局（加载完成前不存在）
+/// - `remaining_bricks`: 剩余砖块数量资源，按生成的可摧毁砖块数量重置
+/// - `config`: 游戏配置，提供尺寸、布局和颜色
+/// - `input_settings`: 输入设置，提示文字按当前输入设备显示发射方式
+/// - `bindings`: 按键绑定，使用键盘时提示文字显示发射键
+/// - `language`: 界面语言
+/// - `theme`: 界面主题，提供道具状态栏的字体
 pub fn setup_game(
     mut commands: Commands,
-    _meshes: ResMut<Assets<Mesh>>,
-    // Bevy 2D 渲染中最常用的一种材质类型。它通常包含一个颜色值和一个可选的纹理引用。用于定义 2D 网格（如精灵 Sprite）的外观。
-    _materials: ResMut<Assets<ColorMaterial>>,
     asset_server: Res<AssetServer>,
     mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     lives: Res<Lives>,
     current_level: Res<Level>,
+    level_catalog: Option<Res<LevelCatalog>>,
+    mut remaining_bricks: ResMut<RemainingBricks>,
+    config: Res<GameConfig>,
+    input_settings: Res<InputSettings>,
+    bindings: Res<KeyBindings>,
+    language: Res<Language>,
+    theme: Res<UiTheme>,
 ) {
     // 注意：相机已在菜单状态创建，此处不再重复创建
     
     // 注意：音效资源现在通过 assets_tracking 模块在应用启动时异步加载
     // 此处不再需要手动加载和插入 CollisionSound 资源
 
-    // 挡板的Y坐标位置  -300
-    let paddle_y = BOTTOM_WALL + GAP_BETWEEN_PADDLE_AND_FLOOR;
+    // 挡板的Y坐标位置  -240
+    let paddle_y = config.paddle_y();
 
     // 生成挡板
     commands.spawn((
-        Sprite::from_color(PADDLE_COLOR, Vec2::ONE),
+        Sprite::from_color(config.paddle_color, Vec2::ONE),
         Transform {
             translation: Vec3::new(0.0, paddle_y, 0.0),
             //.extend(1.0) 将其转换为 Vec3，通常用于 2D 游戏中保持 Z 轴缩放为 1。
-            scale: PADDLE_SIZE.extend(1.0),
+            scale: config.paddle_size.extend(1.0),
             ..default()
         },
         Paddle,
@@ -51,52 +78,24 @@ pub fn setup_game(
         GamePlayRoot,
     ));
 
-    // 计算挡板的Y坐标位置
-    let paddle_y = BOTTOM_WALL + GAP_BETWEEN_PADDLE_AND_FLOOR;
-    
-    // 加载精灵图集纹理 (256x64，4列，每帧64x64)
-    let ball_texture = asset_server.load("images/sprite (1).png");
-    // 创建纹理图集布局：4列1行，每个精灵64x64
-    let atlas_layout = TextureAtlasLayout::from_grid(UVec2::new(64, 64), 4, 1, None, None);
-    let atlas_layout_handle = atlas_layouts.add(atlas_layout);
-    
-    // 生成球（初始附着在挡板上）
-    let ball_y = paddle_y + PADDLE_SIZE.y / 2.0 + BALL_DIAMETER / 2.0 + 5.0;
+    // 生成球（初始附着在挡板上，没有速度）
+    let ball_y = paddle_y + config.attached_ball_height();
     commands.spawn((
-        // 使用精灵图集，显示第0帧（第一列）
-        Sprite::from_atlas_image(
-            ball_texture,
-            TextureAtlas {
-                layout: atlas_layout_handle,
-                index: 0,
-            },
-        ),
-        // 变换组件：控制实体的位置、缩放
-        // 初始位置在挡板正上方，设置缩放使图片大小符合BALL_DIAMETER
-        Transform::from_translation(Vec3::new(0.0, ball_y, 1.0))
-            .with_scale(Vec2::splat(BALL_DIAMETER / 64.0).extend(1.)),
-        Ball,
-        // 初始时小球附着在挡板上，没有速度
+        ball_bundle(&asset_server, &mut atlas_layouts, &config, Vec3::new(0.0, ball_y, 1.0)),
         BallAttached,
-        // 添加动画组件：4帧，每帧0.15秒
-        BallAnimation::new(4, 0.15),
-        GamePlayRoot,
     ));
 
     // 生成游戏提示文字
-    // 计算小球所在的Y坐标（屏幕坐标系，原点在中心，向上为正）
-    let paddle_y = BOTTOM_WALL + GAP_BETWEEN_PADDLE_AND_FLOOR;
-    let ball_y = paddle_y + PADDLE_SIZE.y / 2.0 + BALL_DIAMETER / 2.0 + 5.0;
-    // 文字放在小球上方50像素处
-    let hint_y = ball_y + BALL_DIAMETER / 2.0 + 50.0;
+    // 文字放在小球上方50像素处（屏幕坐标系，原点在中心，向上为正）
+    let hint_y = ball_y + config.ball_diameter / 2.0 + 50.0;
     
     commands.spawn((
-        Text2d::new("按 ↑ 方向键发射小球"),
+        Text2d::new(input_settings.source.launch_hint(&bindings, *language)),
         TextFont {
             font_size: 24.0,
             ..default()
         },
-        TextColor(TEXT_COLOR),
+        TextColor(config.text_color),
         GameHintUi,
         // 使用Transform在世界空间中定位文字
         Transform::from_translation(Vec3::new(0.0, hint_y, 10.0)),
@@ -110,7 +109,7 @@ pub fn setup_game(
             font_size: SCOREBOARD_FONT_SIZE,
             ..default()
         },
-        TextColor(TEXT_COLOR),
+        TextColor(config.text_color),
         ScoreboardUi,
         Node {
             //绝对定位模式，脱离 Bevy 的自动布局流 top/left/bottom/right 控制位置（适合固定在屏幕角落的 UI）
@@ -128,75 +127,141 @@ pub fn setup_game(
                 font_size: SCOREBOARD_FONT_SIZE,
                 ..default()
             },
-            TextColor(SCORE_COLOR),
+            TextColor(config.score_color),
+        ), (
+            // 连击数和得分倍数，由 update_scoreboard 更新
+            TextSpan::default(),
+            TextFont {
+                font_size: COMBO_HUD_FONT_SIZE,
+                ..default()
+            },
+            TextColor(COMBO_HUD_COLOR),
         )],
     ));
 
+    // 生成道具状态栏（记分板下方）
+    spawn_power_up_hud(&mut commands, &theme, &config);
+
     // 生成生命数显示区域（右上角）
-    spawn_lives_ui(&mut commands, lives.0);
+    spawn_lives_ui(&mut commands, lives.0, &config);
 
     // 生成四面墙壁
-    spawn_wall_with_marker(&mut commands, WallLocation::Left);
-    spawn_wall_with_marker(&mut commands, WallLocation::Right);
-    spawn_wall_with_marker(&mut commands, WallLocation::Bottom);
-    spawn_wall_with_marker(&mut commands, WallLocation::Top);
+    spawn_wall_with_marker(&mut commands, WallLocation::Left, &config);
+    spawn_wall_with_marker(&mut commands, WallLocation::Right, &config);
+    spawn_wall_with_marker(&mut commands, WallLocation::Bottom, &config);
+    spawn_wall_with_marker(&mut commands, WallLocation::Top, &config);
 
     // 生成砖块
-    let total_width_of_bricks = (RIGHT_WALL - LEFT_WALL) - 2. * GAP_BETWEEN_BRICKS_AND_SIDES;
-    let bottom_edge_of_bricks = paddle_y + GAP_BETWEEN_PADDLE_AND_BRICKS;
-    let total_height_of_bricks = TOP_WALL - bottom_edge_of_bricks - GAP_BETWEEN_BRICKS_AND_CEILING;
-
-    assert!(total_width_of_bricks > 0.0);
-    assert!(total_height_of_bricks > 0.0);
-
-    // 根据可用空间计算可以放置多少行和列的砖块
-    let n_columns = (total_width_of_bricks / (BRICK_SIZE.x + GAP_BETWEEN_BRICKS)).floor() as usize;
-    // 根据当前关卡决定行数：第一关3行，第二关填满
-    let max_n_rows = (total_height_of_bricks / (BRICK_SIZE.y + GAP_BETWEEN_BRICKS)).floor() as usize;
-    let n_rows = match *current_level {
-        Level::Level1 => max_n_rows.min(3), // 第一关最多3行
-        Level::Level2 => max_n_rows,        // 第二关填满
+    // 砖块区域的宽高为正数由 GameConfig::validate 保证
+    remaining_bricks.0 = 0;
+    let total_width_of_bricks = config.bricks_area_width();
+    let bottom_edge_of_bricks = config.bricks_area_bottom();
+    let total_height_of_bricks = config.bricks_area_height();
+    let brick_size = config.brick_size;
+    let gap_between_bricks = config.gap_between_bricks;
+
+    // 根据可用空间计算最多可以放置多少行和列的砖块
+    let max_n_columns = (total_width_of_bricks / (brick_size.x + gap_between_bricks)).floor() as usize;
+    let max_n_rows = (total_height_of_bricks / (brick_size.y + gap_between_bricks)).floor() as usize;
+
+    // 从关卡目录中取出当前关卡的布局
+    let Some(level) = level_catalog
+        .as_deref()
+        .and_then(|catalog| catalog.get(current_level.0))
+    else {
+        warn!("关卡 {} 不存在或关卡目录尚未加载完成，不生成砖块", current_level.0);
+        return;
     };
-    let n_vertical_gaps = n_columns - 1;
+
+    // 超出竞技场范围的行和列会被裁掉
+    if level.columns() > max_n_columns || level.rows() > max_n_rows {
+        warn!(
+            "关卡 \"{}\" 的网格 {}x{} 超出可用空间 {}x{}，多余的砖块将被忽略",
+            level.name,
+            level.columns(),
+            level.rows(),
+            max_n_columns,
+            max_n_rows
+        );
+    }
+    let n_columns = level.columns().min(max_n_columns);
+    let n_vertical_gaps = n_columns.saturating_sub(1);
 
     // 由于需要四舍五入列数，
     // 砖块顶部和两侧的空间只捕获下限值，而不是精确值
-    let center_of_bricks = (LEFT_WALL + RIGHT_WALL) / 2.0;
+    let center_of_bricks = (config.left_wall + config.right_wall) / 2.0;
     let left_edge_of_bricks = center_of_bricks
         // 砖块占用的空间
-        - (n_columns as f32 / 2.0 * BRICK_SIZE.x)
+        - (n_columns as f32 / 2.0 * brick_size.x)
         // 间隙占用的空间
-        - n_vertical_gaps as f32 / 2.0 * GAP_BETWEEN_BRICKS;
+        - n_vertical_gaps as f32 / 2.0 * gap_between_bricks;
 
     // 在Bevy中，实体的`translation`描述的是中心点，
     // 而不是左下角
-    let offset_x = left_edge_of_bricks + BRICK_SIZE.x / 2.;
-    let offset_y = bottom_edge_of_bricks + BRICK_SIZE.y / 2.;
-
-    // 生成砖块网格
-    for row in 0..n_rows {
-        for column in 0..n_columns {
-            let brick_position = Vec2::new(
-                offset_x + column as f32 * (BRICK_SIZE.x + GAP_BETWEEN_BRICKS),
-                offset_y + row as f32 * (BRICK_SIZE.y + GAP_BETWEEN_BRICKS),
-            );
+    let offset_x = left_edge_of_bricks + brick_size.x / 2.;
+    let offset_y = bottom_edge_of_bricks + brick_size.y / 2.;
 
-            // 生成砖块实体
-            commands.spawn((
-                Sprite {
-                    color: BRICK_COLOR,
-                    ..default()
-                },
-                Transform {
-                    translation: brick_position.extend(0.0),
-                    scale: Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0),
-                    ..default()
-                },
-                Brick,
-                Collider,
-                GamePlayRoot,
-            ));
+    // 按关卡网格生成砖块，row 从砖块区域底部开始计数
+    for (row, column, kind) in level.bricks() {
+        if row >= max_n_rows || column >= n_columns {
+            continue;
         }
+
+        let brick_position = Vec2::new(
+            offset_x + column as f32 * (brick_size.x + gap_between_bricks),
+            offset_y + row as f32 * (brick_size.y + gap_between_bricks),
+        );
+
+        // 钢铁砖块不会被摧毁，不计入剩余砖块数量
+        if spawn_brick(&mut commands, &config, kind, kind.hit_points(), brick_position) {
+            remaining_bricks.0 += 1;
+        }
+    }
+}
+
+/// 生成一个砖块
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `config`: 游戏配置，提供砖块尺寸和颜色
+/// - `kind`: 砖块类型
+/// - `hit_points`: 剩余耐久，钢铁砖块为 None
+/// - `position`: 砖块中心位置
+///
+/// # 返回值
+/// 砖块可以被摧毁（计入剩余砖块数量）时返回 true
+///
+/// # 说明
+/// 开局按关卡网格生成砖块，继续游戏时按存档生成砖块，都通过它生成
+pub fn spawn_brick(
+    commands: &mut Commands,
+    config: &GameConfig,
+    kind: BrickKind,
+    hit_points: Option<u8>,
+    position: Vec2,
+) -> bool {
+    let mut brick = commands.spawn((
+        Sprite {
+            color: kind.color(hit_points.unwrap_or(0), config),
+            ..default()
+        },
+        Transform {
+            translation: position.extend(0.0),
+            scale: config.brick_size.extend(1.0),
+            ..default()
+        },
+        Brick,
+        kind,
+        Collider,
+        GamePlayRoot,
+    ));
+    // 钢铁砖块不会被摧毁，不需要耐久组件
+    match hit_points {
+        Some(hit_points) => {
+            brick.insert(BrickHealth(hit_points));
+            true
+        }
+        None => false,
     }
 }
 
@@ -204,32 +269,27 @@ pub fn setup_game(
 /// 挡板移动系统
 ///
 /// # 参数
-/// - `keyboard_input`: 键盘输入资源，用于检测键盘按键状态
+/// - `player_input`: 本固定时间步长的玩家输入
 /// - `paddle_transform`: 挡板变换组件，用于修改挡板的位置
 /// - `time`: 时间资源，用于获取帧间时间差，实现平滑移动
+/// - `config`: 游戏配置，提供挡板速度和墙壁位置
 pub fn move_paddle(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_input: Res<PlayerInput>,
     mut paddle_transform: Single<&mut Transform, With<Paddle>>,
     time: Res<Time>,
+    config: Res<GameConfig>,
 ) {
-    let mut direction = 0.0;
-
-    // 检测左右方向键输入 pressed持续按下   just_pressed（仅按键按下瞬间触发一次）。
-    if keyboard_input.pressed(KeyCode::ArrowLeft) {
-        direction -= 1.0;
-    }
-
-    if keyboard_input.pressed(KeyCode::ArrowRight) {
-        direction += 1.0;
-    }
+    let direction = player_input.direction;
 
     // 根据玩家输入计算新的挡板水平位置
     let new_paddle_position =
-        paddle_transform.translation.x + direction * PADDLE_SPEED * time.delta_secs();
+        paddle_transform.translation.x + direction * config.paddle_speed * time.delta_secs();
 
     // 更新挡板位置，确保不会离开游戏区域
-    let left_bound = LEFT_WALL + WALL_THICKNESS / 2.0 + PADDLE_SIZE.x / 2.0 + PADDLE_PADDING;
-    let right_bound = RIGHT_WALL - WALL_THICKNESS / 2.0 - PADDLE_SIZE.x / 2.0 - PADDLE_PADDING;
+    // 使用挡板当前的宽度（加宽道具会改变挡板宽度）
+    let paddle_half_width = paddle_transform.scale.x / 2.0;
+    let left_bound = config.left_wall + config.wall_thickness / 2.0 + paddle_half_width + config.paddle_padding;
+    let right_bound = config.right_wall - config.wall_thickness / 2.0 - paddle_half_width - config.paddle_padding;
     // clamp(left_bound, right_bound)：Rust 内置方法，将数值限制在指定区间内，是实现边界限制最简洁的方式。
     paddle_transform.translation.x = new_paddle_position.clamp(left_bound, right_bound);
 }
@@ -237,55 +297,80 @@ pub fn move_paddle(
 /// 应用速度系统，根据速度更新位置
 ///
 /// # 参数
-/// - `query`: 包含Transform和Velocity组件的查询
+/// - `query`: 包含Transform和Velocity组件的查询（道具胶囊、激光等）
 /// - `time`: 时间资源
-pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity), Without<BallAttached>>, time: Res<Time>) {
+///
+/// # 说明
+/// 小球需要扫掠碰撞检测，由 check_for_collisions 系统负责移动
+pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity), (Without<BallAttached>, Without<Ball>)>, time: Res<Time>) {
     for (mut transform, velocity) in &mut query {
         transform.translation.x += velocity.x * time.delta_secs();
         transform.translation.y += velocity.y * time.delta_secs();
     }
 }
 
+/// 在固定时间步长开始时应用状态切换
+///
+/// # 参数
+/// - `world`: 游戏世界
+///
+/// # 说明
+/// Bevy 默认每帧只在 `StateTransition` 调度中切换一次状态，而一帧内可能运行多个固定时间步长。
+/// 游戏逻辑在某一步长中请求的状态切换（发射小球、失去生命、关卡结束）在下一步长开始前生效，
+/// 保证状态切换发生在哪一步长与帧率无关，录像回放才能逐步一致
+pub fn apply_state_transitions(world: &mut World) {
+    let _ = world.try_run_schedule(StateTransition);
+}
+
 /// 移动附着在挡板上的小球系统
 ///
 /// # 参数
 /// - `ball_query`: 附着状态的小球查询
 /// - `paddle_query`: 挡板变换组件查询
+/// - `config`: 游戏配置，提供小球与挡板的相对高度
 pub fn move_attached_ball(
     mut ball_query: Query<&mut Transform, With<BallAttached>>,
     paddle_query: Query<&Transform, (With<Paddle>, Without<BallAttached>)>,
+    config: Res<GameConfig>,
 ) {
     //Bevy 的 Query 方法，要求查询结果必须只有 1 个实体，否则会返回错误（比如有多个挡板时 panic）；
     if let Ok(paddle_transform) = paddle_query.single() {
         for mut ball_transform in &mut ball_query {
             // 小球跟随挡板的X位置，Y位置保持在挡板上方
-            let paddle_y = paddle_transform.translation.y;
-            let ball_y = paddle_y + PADDLE_SIZE.y / 2.0 + BALL_DIAMETER / 2.0 + 5.0;
+            let ball_y = paddle_transform.translation.y + config.attached_ball_height();
             ball_transform.translation.x = paddle_transform.translation.x;
             ball_transform.translation.y = ball_y;
         }
     }
 }
 
-/// 处理小球发射系统（按上方向键发射）
+/// 处理小球发射系统（按发射键发射）
 ///
 /// # 参数
 /// - `commands`: 命令缓冲区
-/// - `keyboard_input`: 键盘输入资源
+/// - `player_input`: 本固定时间步长的玩家输入
 /// - `ball_query`: 附着状态的小球查询
+/// - `config`: 游戏配置，提供小球发射速度
 pub fn handle_ball_launch(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_input: Res<PlayerInput>,
     ball_query: Query<(Entity, &Transform), With<BallAttached>>,
     mut next_state: ResMut<NextState<PlayingState>>,
+    config: Res<GameConfig>,
 ) {
-    // 检测上方向键是否被按下
-    if keyboard_input.just_pressed(KeyCode::ArrowUp) {
-        for (ball_entity, _ball_transform) in &ball_query {
-            // 移除附着状态，添加初始速度（垂直向上）
+    // 检测发射键是否被按下（没有附着的小球时，发射键用于发射激光）
+    if player_input.fire && !ball_query.is_empty() {
+        // 多个小球同时附着时，以垂直向上为中心按 MULTI_BALL_SPREAD 角度散开，避免重叠
+        let count = ball_query.iter().count();
+        for (index, (ball_entity, _ball_transform)) in ball_query.iter().enumerate() {
+            let angle = (index as f32 - (count - 1) as f32 / 2.0) * MULTI_BALL_SPREAD;
+            // 移除附着状态，添加初始速度
             commands.entity(ball_entity).remove::<BallAttached>();
-            commands.entity(ball_entity).insert(Velocity(Vec2::new(0.0, BALL_SPEED)));
+            commands
+                .entity(ball_entity)
+                .insert(Velocity(Vec2::from_angle(angle).rotate(Vec2::new(0.0, config.ball_speed))));
         }
+        commands.trigger(BallLaunched);
         // 切换到游戏进行中状态
         next_state.set(PlayingState::ball_launched);
     }
@@ -315,106 +400,274 @@ pub fn update_hint_visibility(
 ///
 /// # 参数
 /// - `score`: 分数资源
+/// - `combo`: 连击资源
+/// - `language`: 界面语言
 /// - `score_root`: 记分板根实体
 /// - `writer`: 文本UI写入器
+///
+/// # 说明
+/// 分数后面显示连击数和得分倍数，连击数不足 2 时不显示
 pub fn update_scoreboard(
     score: Res<Score>,
+    combo: Res<Combo>,
+    language: Res<Language>,
     score_root: Single<Entity, (With<ScoreboardUi>, With<Text>)>,
     mut writer: TextUiWriter,
 ) {
     *writer.text(*score_root, 1) = score.to_string();
+    let combo_text = if combo.0 >= 2 {
+        format!("  {} {} ×{}", language.tr("连击"), combo.0, combo.multiplier())
+    } else {
+        String::new()
+    };
+    if *writer.text(*score_root, 2) != combo_text {
+        *writer.text(*score_root, 2) = combo_text;
+    }
 }
 
-/// 碰撞检测系统
+/// 砖块计分系统（观察者）
 ///
 /// # 参数
-/// - `commands`: 命令缓冲区
+/// - `destroyed`: 砖块被摧毁事件
 /// - `score`: 分数资源
+/// - `combo`: 连击资源
+///
+/// # 说明
+/// 每摧毁一个砖块（包括被爆炸波及的砖块）获得砖块的基础得分（见 [`BrickKind::points`]）× 当前的连击倍数，
+/// 然后连击数加 1
+pub fn award_brick_points(destroyed: On<BrickDestroyed>, mut score: ResMut<Score>, mut combo: ResMut<Combo>) {
+    **score += destroyed.kind.points() * combo.multiplier();
+    **combo += 1;
+}
+
+/// 砖块受损时连击数加 1（观察者）
+///
+/// # 参数
+/// - `damaged`: 砖块受损事件
+/// - `combo`: 连击资源
+///
+/// # 说明
+/// 钢铁砖块不会被摧毁，击中它不计入连击
+pub fn extend_combo(damaged: On<BrickDamaged>, mut combo: ResMut<Combo>) {
+    if damaged.remaining.is_some() {
+        **combo += 1;
+    }
+}
+
+/// 连击中断（观察者），在事件 `E` 触发时把连击数清零
+///
+/// # 参数
+/// - `_event`: 中断连击的事件（小球碰到挡板、失去生命）
+/// - `combo`: 连击资源
+pub fn break_combo<E: Event>(_event: On<E>, mut combo: ResMut<Combo>) {
+    combo.0 = 0;
+}
+
+/// 小球加速系统（观察者），小球每碰到一次挡板加速一次
+///
+/// # 参数
+/// - `_hit`: 小球碰到挡板事件
+/// - `progression`: 小球加速资源
+///
+/// # 说明
+/// 只记录碰撞次数，小球速度由 [`sync_ball_speed`](super::power_up::sync_ball_speed) 按难度计算
+pub fn speed_up_ball(_hit: On<BallHitPaddle>, mut progression: ResMut<SpeedProgression>) {
+    **progression += 1;
+}
+
+/// 失去生命后小球恢复初始速度（观察者）
+///
+/// # 参数
+/// - `_lost`: 失去生命事件
+/// - `progression`: 小球加速资源
+pub fn reset_ball_speed(_lost: On<LifeLost>, mut progression: ResMut<SpeedProgression>) {
+    progression.0 = 0;
+}
+
+/// 通关奖励系统（观察者）
+///
+/// # 参数
+/// - `_cleared`: 通关事件
+/// - `score`: 分数资源
+/// - `lives`: 生命数资源
+/// - `clock`: 关卡计时
+/// - `bonus`: 通关奖励资源，记录本次奖励供胜利界面显示
+///
+/// # 说明
+/// 在进入胜利状态之前加分，因此胜利界面、排行榜和战役成绩中的分数都包含通关奖励
+pub fn award_clear_bonus(
+    _cleared: On<LevelCleared>,
+    mut score: ResMut<Score>,
+    lives: Res<Lives>,
+    clock: Res<LevelClock>,
+    mut bonus: ResMut<ClearBonus>,
+) {
+    *bonus = ClearBonus::new(lives.0, clock.elapsed_secs());
+    **score += bonus.total();
+}
+
+/// 关卡计时系统（固定时间步长）
+///
+/// # 参数
+/// - `time`: 时间资源（在 FixedUpdate 中为固定步长）
+/// - `clock`: 关卡计时
+pub fn tick_level_clock(time: Res<Time>, mut clock: ResMut<LevelClock>) {
+    clock.tick(time.delta());
+}
+
+/// 重置关卡计分状态系统（进入游戏状态时调用）
+///
+/// # 参数
+/// - `combo`: 连击资源
+/// - `clock`: 关卡计时
+/// - `bonus`: 通关奖励资源
+/// - `progression`: 小球加速资源
+///
+/// # 说明
+/// 每一关（包括重试）都从零连击、零用时和小球初始速度开始；继续游戏时由 restore_run 按快照恢复
+pub fn reset_level_scoring(
+    mut combo: ResMut<Combo>,
+    mut clock: ResMut<LevelClock>,
+    mut bonus: ResMut<ClearBonus>,
+    mut progression: ResMut<SpeedProgression>,
+) {
+    combo.0 = 0;
+    clock.reset();
+    *bonus = ClearBonus::default();
+    progression.0 = 0;
+}
+
+/// 小球移动与碰撞检测系统（固定时间步长）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `time`: 时间资源（在 FixedUpdate 中为固定步长）
+/// - `remaining_bricks`: 剩余砖块数量，归零时通关
 /// - `lives`: 生命数资源
-/// - `ball_query`: 球实体查询
-/// - `collider_query`: 碰撞器实体查询（包含墙壁位置信息）
+/// - `rng`: 随机数生成器，用于决定砖块是否掉落道具
+/// - `active_power_ups`: 当前生效的道具，粘性挡板生效时小球会被挡板粘住
+/// - `ball_query`: 运动中的小球查询
+/// - `attached_ball_query`: 附着在挡板上的小球查询
+/// - `collider_query`: 碰撞器实体查询（包含砖块类型和墙壁位置信息）
+/// - `brick_health_query`: 砖块耐久查询
 /// - `paddle_query`: 挡板查询（用于检测是否碰撞到挡板）
 /// - `next_playing_state`: 下一个游戏进行中状态
 /// - `next_game_state`: 下一个游戏状态
+/// - `config`: 游戏配置，提供小球直径、爆炸半径和砖块颜色
+/// - `run`: 本局游戏进度，难度决定小球从挡板反弹的最大角度
 ///
 /// # 逻辑
-/// 检测小球与各种碰撞体的碰撞：
-/// - 砖块：销毁砖块并增加分数
+/// 小球的移动由本系统负责（apply_velocity 不移动小球），使用扫掠检测避免高速穿透：
+/// 1. 计算本步长的位移，找出位移路径上最早碰到的一个碰撞体（见 [`nearest_contact`]）
+/// 2. 小球移动到碰撞位置（已经嵌入碰撞体时先推出），处理碰撞并改变速度
+/// 3. 以新速度继续移动本步长剩余的时间，最多处理 MAX_BALL_BOUNCES_PER_STEP 次碰撞
+///
+/// 碰撞处理：
+/// - 砖块：扣除耐久，耐久归零时销毁砖块；钢铁砖块只反弹；爆炸砖块会连带摧毁周围的砖块
 /// - 挡板：根据击中位置改变反弹角度
-/// - 底部墙壁：触发失败逻辑，减少生命数
+/// - 底部墙壁：销毁小球，场上最后一个小球掉落时减少生命数
 /// - 其他墙壁：标准反射
+///
+/// 碰撞的结果以事件的形式通知其他系统（音效、计分等），本系统不直接处理：
+/// [`BallHitPaddle`]、[`BallHitWall`]、[`BrickDamaged`]、[`BrickDestroyed`]、[`LifeLost`]、[`LevelCleared`]
 pub fn check_for_collisions(
     mut commands: Commands,
-    mut score: ResMut<Score>,
+    time: Res<Time>,
+    mut remaining_bricks: ResMut<RemainingBricks>,
     mut lives: ResMut<Lives>,
+    mut rng: ResMut<GameRng>,
+    active_power_ups: Res<ActivePowerUps>,
+    config: Res<GameConfig>,
+    run: Res<RunProgress>,
     // 排除附着在挡板上的小球，避免与 move_attached_ball 系统冲突
-    ball_query: Single<(Entity, &mut Velocity, &Transform), (With<Ball>, Without<BallAttached>)>,
-    // 查询所有碰撞体：实体ID、位置、是否是砖块（Option<&Brick>）、墙壁位置（Option<&WallLocation>），筛选带Collider标签的实体
-    collider_query: Query<(Entity, &Transform, Option<&Brick>, Option<&WallLocation>), With<Collider>>,
-    paddle_query: Query<&Transform, With<Paddle>>,
+    mut ball_query: Query<(Entity, &mut Velocity, &mut Transform), (With<Ball>, Without<BallAttached>)>,
+    // 附着在挡板上的小球（粘性挡板），仍然算作场上的小球
+    attached_ball_query: Query<(), (With<Ball>, With<BallAttached>)>,
+    // 查询所有碰撞体：实体ID、位置、砖块类型（Option<&BrickKind>，非砖块为None）、墙壁位置（Option<&WallLocation>），筛选带Collider标签的实体
+    collider_query: ColliderQuery,
+    // 砖块的耐久和精灵，用于扣除耐久并更新颜色
+    mut brick_health_query: Query<(&mut BrickHealth, &mut Sprite)>,
+    paddle_query: Query<&Transform, (With<Paddle>, Without<Ball>)>,
     mut next_playing_state: ResMut<NextState<PlayingState>>,
     mut next_game_state: ResMut<NextState<GameState>>,
 ) {
-    // 解包小球的实体ID、速度（可变）和位置组件
-    //into_inner() 的作用：将 Bevy Query 返回的 "结果包装类型"转换为直接可操作的引用 / 值，确保单个
-    let (ball_entity, mut ball_velocity, ball_transform) = ball_query.into_inner();
-
-    // 遍历所有碰撞体（墙、球拍、砖块）
-    for (collider_entity, collider_transform, maybe_brick, maybe_wall_location) in &collider_query {
-        // 检测球与碰撞器的碰撞
-        // ball_collision 是检测圆形（小球）与轴对齐矩形（AABB，碰撞体） 碰撞的核心函数，返回 Option<Collision>（None = 无碰撞，Some = 碰撞方向）。
-        //enum Collision {
-        //     Left,   // 小球撞到矩形的左侧
-        //     Right,  // 小球撞到矩形的右侧
-        //     Top,    // 小球撞到矩形的上侧
-        //     Bottom, // 小球撞到矩形的下侧
-        // }
-        let collision = ball_collision(
-            // BoundingCircle	小球碰撞盒	坐标（Vec2） + 半径（f32）
-            BoundingCircle::new(ball_transform.translation.truncate(), BALL_DIAMETER / 2.),
-            //Aabb2d	碰撞体碰撞盒	坐标（Vec2） + 半尺寸（Vec2）
-            Aabb2d::new(
-                collider_transform.translation.truncate(),  //将 Vec3（x/y/z）转为 Vec2（x/y），去掉 z 轴（2D 碰撞不需要 z 轴）；
-                collider_transform.scale.truncate() / 2.,
-            ),
-        );
+    // 本步长已被摧毁的砖块，despawn 命令要到步长结束才生效，查询结果中仍然包含它们
+    let mut destroyed_bricks = EntityHashSet::default();
+    // 场上剩余的小球数量（包括附着在挡板上的小球），归零时才失去一条生命
+    let mut balls_in_play = ball_query.iter().count() + attached_ball_query.iter().count();
 
-        if let Some(collision) = collision {
-            // 触发碰撞事件
-            commands.trigger(BallCollided);
+    // 遍历所有运动中的小球
+    'balls: for (ball_entity, mut ball_velocity, mut ball_transform) in &mut ball_query {
+        // 本步长内剩余的移动时间
+        let mut remaining = time.delta_secs();
+
+        for _ in 0..MAX_BALL_BOUNCES_PER_STEP {
+            let start = ball_transform.translation.truncate();
+            let displacement = ball_velocity.0 * remaining;
+
+            // 找出位移路径上最先碰到的碰撞体（已摧毁的砖块不参与检测），每次只处理一个接触
+            // nearest_contact 返回碰撞时间（0.0 = 起点，1.0 = 终点）和碰撞的边（Left/Right/Top/Bottom）
+            let earliest = nearest_contact(
+                // BoundingCircle	小球碰撞盒	坐标（Vec2） + 半径（f32）
+                BoundingCircle::new(start, config.ball_diameter / 2.),
+                displacement,
+                collider_query
+                    .iter()
+                    .filter(|(entity, ..)| !destroyed_bricks.contains(entity))
+                    .map(|(entity, collider_transform, maybe_brick, maybe_wall_location)| {
+                        (
+                            (entity, maybe_brick, maybe_wall_location),
+                            //Aabb2d	碰撞体碰撞盒	坐标（Vec2） + 半尺寸（Vec2）
+                            Aabb2d::new(
+                                collider_transform.translation.truncate(),  //将 Vec3（x/y/z）转为 Vec2（x/y），去掉 z 轴（2D 碰撞不需要 z 轴）；
+                                collider_transform.scale.truncate() / 2.,
+                            ),
+                        )
+                    }),
+            );
+
+            let Some(((collider_entity, maybe_brick, maybe_wall_location), hit)) = earliest else {
+                // 路径上没有碰撞体，移动完剩余的距离
+                ball_transform.translation += displacement.extend(0.0);
+                break;
+            };
+
+            // 移动到碰撞位置（起点已经嵌入碰撞体时先推出），剩余的时间在处理碰撞后继续移动
+            ball_transform.translation += (displacement * hit.time + hit.push).extend(0.0);
+            remaining *= 1.0 - hit.time;
+            let collision = hit.side;
 
             // 检查是否碰撞到底部墙壁（失败条件）
-            let is_bottom_wall = maybe_wall_location.map_or(false, |loc| matches!(loc, WallLocation::Bottom));
-            
+            let is_bottom_wall = maybe_wall_location.is_some_and(|loc| matches!(loc, WallLocation::Bottom));
+
             if is_bottom_wall {
-                // 小球碰到底部墙壁，触发失败逻辑
-                // 减少生命数
-                lives.0 = lives.0.saturating_sub(1);
-
-                if lives.0 == 0 {
-                    // 生命数归零，游戏结束
-                    next_game_state.set(GameState::GameOver);
-                } else {
-                    // 还有剩余生命，销毁当前小球并重置
-                    commands.entity(ball_entity).despawn();
-                    next_playing_state.set(PlayingState::ball_attached);
-                }
-                // 底部墙壁碰撞后不再执行其他碰撞逻辑
-                return;
+                // 小球碰到底部墙壁，销毁这个小球
+                // 只有最后一个小球掉落时才触发失败逻辑
+                commands.entity(ball_entity).despawn();
+                balls_in_play -= 1;
+                // 这个小球已被销毁，不再继续移动
+                continue 'balls;
             }
 
-            // 如果是砖块，销毁它并增加分数
-            if maybe_brick.is_some() {
-                commands.entity(collider_entity).despawn();
-                **score += 1;
-
-                // 检查是否还有剩余砖块
-                // 通过查询所有带有Brick组件的实体来统计剩余砖块数量
-                let remaining_bricks = collider_query.iter().filter(|(_, _, brick, _)| brick.is_some()).count();
-                // 注意：当前被碰撞的砖块已经被despawn，但查询结果中仍然包含它
-                // 所以剩余砖块数量为1时表示这是最后一个砖块
-                if remaining_bricks <= 1 {
+            // 如果是砖块，扣除耐久，耐久归零时摧毁它（钢铁砖块只反弹）
+            if let Some(&brick_kind) = maybe_brick {
+                let broken = damage_brick(
+                    &mut commands,
+                    &mut remaining_bricks,
+                    &mut rng,
+                    &mut destroyed_bricks,
+                    &collider_query,
+                    &mut brick_health_query,
+                    &config,
+                    collider_entity,
+                    brick_kind,
+                    Some(ball_velocity.length()),
+                );
+
+                // 检查是否还有剩余的可摧毁砖块（钢铁砖块不计入）
+                if broken && remaining_bricks.0 == 0 {
                     // 所有砖块被消灭，切换到胜利状态
+                    commands.trigger(LevelCleared);
                     next_game_state.set(GameState::Victory);
                     return;
                 }
@@ -422,32 +675,54 @@ pub fn check_for_collisions(
 
             // 检查是否是挡板（不是砖块，且碰撞方向是顶部）
             let is_paddle = maybe_brick.is_none() && collision == Collision::Top;
-            
+
             if is_paddle {
                 // 挡板碰撞：根据击中位置改变反弹角度
                 if let Ok(paddle_transform) = paddle_query.single() {
                     // 计算小球击中挡板的相对位置（-1.0到1.0，0表示中间）
                     let paddle_center = paddle_transform.translation.x;
                     let ball_x = ball_transform.translation.x;
-                    let paddle_half_width = PADDLE_SIZE.x / 2.0;
-                    
+                    // 使用挡板当前的宽度（加宽道具会改变挡板宽度）
+                    let paddle_half_width = paddle_transform.scale.x / 2.0;
+
                     // 计算相对位置，范围 [-1, 1]
                     let relative_hit_pos = ((ball_x - paddle_center) / paddle_half_width).clamp(-1.0, 1.0);
-                    
+
                     // 根据击中位置计算反弹角度
                     // 中间击中：垂直向上（0度）
-                    // 边缘击中：最大60度倾斜
-                    let max_angle = std::f32::consts::PI / 3.0; // 60度
+                    // 边缘击中：最大倾斜角度由难度决定（普通难度为60度）
+                    let max_angle = run.difficulty.max_bounce_angle();
                     let bounce_angle = relative_hit_pos * max_angle;
-                    
-                    // 计算新的速度方向
-                    let new_velocity_x = bounce_angle.sin() * BALL_SPEED;
-                    let new_velocity_y = bounce_angle.cos() * BALL_SPEED;
-                    
+
+                    // 计算新的速度方向，保持当前的速度大小（减速道具会改变速度大小）
+                    let speed = ball_velocity.length();
+                    let new_velocity_x = bounce_angle.sin() * speed;
+                    let new_velocity_y = bounce_angle.cos() * speed;
+
                     ball_velocity.x = new_velocity_x;
                     ball_velocity.y = new_velocity_y.abs(); // 确保向上反弹
+
+                    commands.trigger(BallHitPaddle {
+                        offset: relative_hit_pos,
+                        speed,
+                        position: ball_transform.translation.truncate(),
+                    });
+
+                    // 粘性挡板：小球被粘在挡板上，等待玩家重新发射
+                    if active_power_ups.is_active(PowerUpKind::StickyPaddle) {
+                        commands.entity(ball_entity).insert(BallAttached);
+                        continue 'balls;
+                    }
                 }
             } else {
+                // 砖块的碰撞事件由 damage_brick 触发
+                if let Some(&side) = maybe_wall_location {
+                    commands.trigger(BallHitWall {
+                        side,
+                        position: ball_transform.translation.truncate(),
+                        speed: ball_velocity.length(),
+                    });
+                }
                 // 墙壁和砖块碰撞：使用标准反射逻辑
                 // 根据碰撞方向反射球的速度
                 let mut reflect_x = false;
@@ -474,25 +749,166 @@ pub fn check_for_collisions(
             }
         }
     }
+
+    if balls_in_play == 0 {
+        // 最后一个小球也掉落了，减少生命数
+        lives.0 = lives.0.saturating_sub(1);
+        commands.trigger(LifeLost);
+
+        if lives.0 == 0 {
+            // 生命数归零，游戏结束
+            next_game_state.set(GameState::GameOver);
+        } else {
+            // 还有剩余生命，重置小球
+            next_playing_state.set(PlayingState::ball_attached);
+        }
+    }
 }
 
-/// 播放碰撞音效系统
+/// 对砖块造成一次伤害
 ///
 /// # 参数
-/// - `_collided`: 碰撞事件
 /// - `commands`: 命令缓冲区
-/// - `sound`: 碰撞音效资源
-pub fn play_collision_sound(
-    _collided: On<BallCollided>,
-    mut commands: Commands,
-    sound: Res<GameSounds>,
-) {
-    // 音效播放器组件：关联要播放的音效资源
-    // 播放设置：播放完成后自动销毁播放器实体
-    commands.spawn((AudioPlayer(sound.collision.clone()), PlaybackSettings::DESPAWN));
+/// - `remaining_bricks`: 剩余砖块数量，砖块被摧毁时减少
+/// - `rng`: 随机数生成器，用于决定是否掉落道具
+/// - `destroyed_bricks`: 本步长已被摧毁的砖块集合
+/// - `collider_query`: 碰撞器实体查询，用于查找爆炸范围内的砖块
+/// - `brick_health_query`: 砖块耐久查询
+/// - `config`: 游戏配置，提供砖块颜色和爆炸半径
+/// - `brick_entity`: 被击中的砖块
+/// - `brick_kind`: 被击中的砖块类型
+/// - `ball_speed`: 击中砖块的小球的速度大小，激光击中时为 None，随事件传给音效
+///
+/// # 返回值
+/// 砖块因此次伤害被摧毁时返回 true；钢铁砖块和本步长已被摧毁的砖块不受影响，返回 false
+///
+/// # 说明
+/// 砖块受损时触发 [`BrickDamaged`] 事件，被摧毁时触发 [`BrickDestroyed`] 事件（本步长已被摧毁的砖块除外）
+pub fn damage_brick(
+    commands: &mut Commands,
+    remaining_bricks: &mut RemainingBricks,
+    rng: &mut GameRng,
+    destroyed_bricks: &mut EntityHashSet,
+    collider_query: &ColliderQuery,
+    brick_health_query: &mut Query<(&mut BrickHealth, &mut Sprite)>,
+    config: &GameConfig,
+    brick_entity: Entity,
+    brick_kind: BrickKind,
+    ball_speed: Option<f32>,
+) -> bool {
+    if destroyed_bricks.contains(&brick_entity) {
+        return false;
+    }
+    let position = collider_query
+        .get(brick_entity)
+        .map(|(_, transform, _, _)| transform.translation.truncate())
+        .unwrap_or_default();
+    if !brick_kind.is_destructible() {
+        commands.trigger(BrickDamaged {
+            entity: brick_entity,
+            position,
+            kind: brick_kind,
+            remaining: None,
+            speed: ball_speed,
+        });
+        return false;
+    }
+
+    let remaining = match brick_health_query.get_mut(brick_entity) {
+        Ok((mut health, mut sprite)) => {
+            health.0 = health.0.saturating_sub(1);
+            sprite.color = brick_kind.color(health.0, config);
+            health.0
+        }
+        Err(_) => 0,
+    };
+
+    let broken = remaining == 0;
+    if broken {
+        destroy_brick(
+            commands,
+            remaining_bricks,
+            rng,
+            destroyed_bricks,
+            collider_query,
+            config,
+            brick_entity,
+            ball_speed,
+        );
+    } else {
+        commands.trigger(BrickDamaged {
+            entity: brick_entity,
+            position,
+            kind: brick_kind,
+            remaining: Some(remaining),
+            speed: ball_speed,
+        });
+    }
+    broken
 }
 
+/// 摧毁砖块，每个被摧毁的砖块触发一次 [`BrickDestroyed`] 事件
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `remaining_bricks`: 剩余砖块数量
+/// - `rng`: 随机数生成器，用于决定是否掉落道具
+/// - `destroyed_bricks`: 本步长已被摧毁的砖块集合
+/// - `collider_query`: 碰撞器实体查询，用于查找爆炸范围内的砖块
+/// - `config`: 游戏配置，提供爆炸半径
+/// - `brick_entity`: 要摧毁的砖块
+/// - `ball_speed`: 击中砖块的小球的速度大小，只有被直接击中的砖块的事件带有速度
+///
+/// # 说明
+/// 爆炸砖块被摧毁时，会摧毁爆炸半径内所有可摧毁的砖块（无视剩余耐久），
+/// 被波及的爆炸砖块会继续引发连锁爆炸。每个被摧毁的砖块都有概率掉落道具
+fn destroy_brick(
+    commands: &mut Commands,
+    remaining_bricks: &mut RemainingBricks,
+    rng: &mut GameRng,
+    destroyed_bricks: &mut EntityHashSet,
+    collider_query: &ColliderQuery,
+    config: &GameConfig,
+    brick_entity: Entity,
+    ball_speed: Option<f32>,
+) {
+    // 待处理的砖块队列，用于处理连锁爆炸
+    let mut pending = vec![brick_entity];
+    while let Some(entity) = pending.pop() {
+        if !destroyed_bricks.insert(entity) {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        remaining_bricks.0 = remaining_bricks.0.saturating_sub(1);
 
+        let Ok((_, transform, brick_kind, _)) = collider_query.get(entity) else {
+            continue;
+        };
+        let center = transform.translation.truncate();
+        if let Some(&kind) = brick_kind {
+            commands.trigger(BrickDestroyed {
+                entity,
+                position: center,
+                kind,
+                speed: if entity == brick_entity { ball_speed } else { None },
+            });
+        }
+        try_drop_power_up(commands, rng, center);
+
+        if brick_kind != Some(&BrickKind::Explosive) {
+            continue;
+        }
+        // 爆炸：摧毁半径内所有可摧毁的砖块
+        for (other, other_transform, other_kind, _) in collider_query {
+            if other_kind.is_some_and(|kind| kind.is_destructible())
+                && !destroyed_bricks.contains(&other)
+                && other_transform.translation.truncate().distance(center) <= config.explosion_radius
+            {
+                pending.push(other);
+            }
+        }
+    }
+}
 
 /// 检测球与边界框的碰撞
 ///
@@ -502,6 +918,9 @@ pub fn play_collision_sound(
 ///
 /// # 返回值
 /// 如果发生碰撞，返回Some(Collision)表示碰撞的边；否则返回None
+///
+/// # 说明
+/// 球心已经进入矩形内部时，取离球心最近的那条边
 pub fn ball_collision(ball: BoundingCircle, bounding_box: Aabb2d) -> Option<Collision> {
     //判断圆形是否与矩形有重叠区域（相交）。
     //如果小球完全在矩形外 → 返回 false，函数直接返回 None；
@@ -516,6 +935,21 @@ pub fn ball_collision(ball: BoundingCircle, bounding_box: Aabb2d) -> Option<Coll
     //球心相对于矩形最近点的位置
     let offset = ball.center() - closest;
 
+    // 球心在矩形内部时 offset 为零，改用球心到四条边的距离判断
+    if offset == Vec2::ZERO {
+        let center = ball.center();
+        let distances = [
+            (center.x - bounding_box.min.x, Collision::Left),
+            (bounding_box.max.x - center.x, Collision::Right),
+            (bounding_box.max.y - center.y, Collision::Top),
+            (center.y - bounding_box.min.y, Collision::Bottom),
+        ];
+        return distances
+            .into_iter()
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, side)| side);
+    }
+
     // 根据偏移量判断碰撞的边
     let side = if offset.x.abs() > offset.y.abs() {
         if offset.x < 0. {
@@ -532,12 +966,196 @@ pub fn ball_collision(ball: BoundingCircle, bounding_box: Aabb2d) -> Option<Coll
     Some(side)
 }
 
+/// 计算把小球推出矩形所需的最小位移
+///
+/// # 参数
+/// - `ball`: 球的边界圆
+/// - `bounding_box`: 边界框
+/// - `side`: 碰撞的边（由 [`ball_collision`] 得出）
+///
+/// # 返回值
+/// 小球加上这个位移后恰好与矩形接触；没有重叠时返回零向量
+pub fn depenetration(ball: BoundingCircle, bounding_box: Aabb2d, side: Collision) -> Vec2 {
+    let center = ball.center();
+    let radius = ball.radius();
+    let offset = center - bounding_box.closest_point(center);
+    let distance = offset.length();
+
+    if distance > 0.0 {
+        // 球心在矩形外：沿最近点指向球心的方向推出
+        offset / distance * (radius - distance).max(0.0)
+    } else {
+        // 球心在矩形内：沿碰撞边的法线方向推出
+        match side {
+            Collision::Left => Vec2::new(bounding_box.min.x - radius - center.x, 0.0),
+            Collision::Right => Vec2::new(bounding_box.max.x + radius - center.x, 0.0),
+            Collision::Top => Vec2::new(0.0, bounding_box.max.y + radius - center.y),
+            Collision::Bottom => Vec2::new(0.0, bounding_box.min.y - radius - center.y),
+        }
+    }
+}
+
+/// 扫掠碰撞结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// 碰撞发生的时间，以位移的比例表示（0.0 = 起点，1.0 = 终点）
+    pub time: f32,
+    /// 小球撞到矩形的哪一侧
+    pub side: Collision,
+    /// 起点已经与矩形重叠时，把小球推出矩形所需的位移；否则为零向量
+    pub push: Vec2,
+}
+
+/// 扫掠检测：小球沿位移移动时，找出最早与矩形接触的时间
+///
+/// # 参数
+/// - `ball`: 小球在起点处的包围圆
+/// - `displacement`: 小球在本次移动中的位移
+/// - `bounding_box`: 碰撞体的包围盒
+///
+/// # 返回值
+/// 位移路径上与矩形接触时返回碰撞时间和碰撞的边，否则返回 None
+///
+/// # 说明
+/// 圆与矩形的扫掠检测等价于球心这一点与"矩形向外扩展半径后的圆角矩形"做射线检测：
+/// - 先与扩展后的矩形做射线检测（slab 方法），得到进入时间和碰撞的边
+/// - 如果进入点位于圆角区域，再与以矩形角点为圆心的圆做射线检测
+///
+/// 起点已经与矩形重叠时，只有小球朝矩形内部运动才算碰撞（时间为 0，并给出推出位移），
+/// 避免小球离开时被反复反弹
+pub fn ball_sweep(ball: BoundingCircle, displacement: Vec2, bounding_box: Aabb2d) -> Option<SweepHit> {
+    let start = ball.center();
+    let radius = ball.radius();
+
+    // 起点已经重叠：沿用离散检测的碰撞方向
+    if let Some(side) = ball_collision(ball, bounding_box) {
+        let moving_in = match side {
+            Collision::Left => displacement.x > 0.0,
+            Collision::Right => displacement.x < 0.0,
+            Collision::Top => displacement.y < 0.0,
+            Collision::Bottom => displacement.y > 0.0,
+        };
+        return moving_in.then(|| SweepHit {
+            time: 0.0,
+            side,
+            push: depenetration(ball, bounding_box, side),
+        });
+    }
+
+    // 矩形向外扩展小球半径
+    let min = bounding_box.min - Vec2::splat(radius);
+    let max = bounding_box.max + Vec2::splat(radius);
+
+    // slab 方法：分别计算两个轴上进入和离开扩展矩形的时间
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    let mut side = None;
+    for (start, delta, min, max, near_side, far_side) in [
+        (start.x, displacement.x, min.x, max.x, Collision::Left, Collision::Right),
+        (start.y, displacement.y, min.y, max.y, Collision::Bottom, Collision::Top),
+    ] {
+        if delta == 0.0 {
+            // 这个轴上没有移动，起点必须在范围内
+            if start < min || start > max {
+                return None;
+            }
+            continue;
+        }
+        let t_min = (min - start) / delta;
+        let t_max = (max - start) / delta;
+        // 向正方向移动时先碰到矩形的"近侧"（左侧或底部）
+        let (near, far, hit_side) = if delta > 0.0 {
+            (t_min, t_max, near_side)
+        } else {
+            (t_max, t_min, far_side)
+        };
+        if near > t_enter {
+            t_enter = near;
+            side = Some(hit_side);
+        }
+        t_exit = t_exit.min(far);
+    }
+
+    if t_enter > t_exit || t_enter > 1.0 || t_exit < 0.0 {
+        return None;
+    }
+    let mut side = side?;
+    let mut time = t_enter.max(0.0);
+
+    // 进入点在矩形的角落外侧（两个轴都超出原矩形）时，实际接触的是角点处的圆弧
+    let point = start + displacement * time;
+    let outside_x = point.x < bounding_box.min.x || point.x > bounding_box.max.x;
+    let outside_y = point.y < bounding_box.min.y || point.y > bounding_box.max.y;
+    if outside_x && outside_y {
+        let corner = point.clamp(bounding_box.min, bounding_box.max);
+        // 求解 |start + displacement * t - corner| = radius 的较小根
+        let offset = start - corner;
+        let a = displacement.length_squared();
+        let b = 2.0 * offset.dot(displacement);
+        let c = offset.length_squared() - radius * radius;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        time = (-b - discriminant.sqrt()) / (2.0 * a);
+        if !(0.0..=1.0).contains(&time) {
+            return None;
+        }
+        // 根据接触点相对角点的方向判断碰撞的边（与 ball_collision 一致）
+        let contact = start + displacement * time - corner;
+        side = if contact.x.abs() > contact.y.abs() {
+            if contact.x < 0. { Collision::Left } else { Collision::Right }
+        } else if contact.y > 0. {
+            Collision::Top
+        } else {
+            Collision::Bottom
+        };
+    }
+
+    Some(SweepHit { time, side, push: Vec2::ZERO })
+}
+
+/// 在多个碰撞体中找出小球最先接触的一个
+///
+/// # 参数
+/// - `ball`: 小球在起点处的包围圆
+/// - `displacement`: 小球在本次移动中的位移
+/// - `colliders`: 碰撞体迭代器，每项为 `(标识, 包围盒)`
+///
+/// # 返回值
+/// 最近的接触及其碰撞体标识；路径上没有碰撞体时返回 None
+///
+/// # 说明
+/// 每次只处理一个接触，避免同时碰到相邻的两块砖块时速度被反射两次。
+/// 接触时间相同时（例如小球正好打在两块砖块的缝隙上），依次取重叠更深的、包围盒中心离小球更近的
+pub fn nearest_contact<T>(
+    ball: BoundingCircle,
+    displacement: Vec2,
+    colliders: impl IntoIterator<Item = (T, Aabb2d)>,
+) -> Option<(T, SweepHit)> {
+    colliders
+        .into_iter()
+        .filter_map(|(id, bounding_box)| {
+            let hit = ball_sweep(ball, displacement, bounding_box)?;
+            let distance = bounding_box.center().distance_squared(ball.center());
+            Some((id, hit, distance))
+        })
+        .min_by(|(_, a, a_distance), (_, b, b_distance)| {
+            a.time
+                .total_cmp(&b.time)
+                .then(b.push.length_squared().total_cmp(&a.push.length_squared()))
+                .then(a_distance.total_cmp(b_distance))
+        })
+        .map(|(id, hit, _)| (id, hit))
+}
+
 /// 生成生命数UI显示
 ///
 /// # 参数
 /// - `commands`: 命令缓冲区，用于创建实体
 /// - `lives_count`: 当前生命数量
-fn spawn_lives_ui(commands: &mut Commands, lives_count: usize) {
+/// - `config`: 游戏配置，生命指示器使用小球颜色
+fn spawn_lives_ui(commands: &mut Commands, lives_count: usize, config: &GameConfig) {
     // 创建生命指示器容器
     let mut container = commands.spawn((
         Node {
@@ -563,7 +1181,7 @@ fn spawn_lives_ui(commands: &mut Commands, lives_count: usize) {
                     border_radius: BorderRadius::all(Val::Px(LIFE_INDICATOR_SIZE / 2.0)),
                     ..default()
                 },
-                BackgroundColor(BALL_COLOR),
+                BackgroundColor(config.ball_color),
                 LifeIndicator,
             ));
         });
@@ -577,9 +1195,11 @@ fn spawn_lives_ui(commands: &mut Commands, lives_count: usize) {
 /// - `lives`: 生命数资源
 /// - `lives_ui_query`: 生命数UI容器查询
 /// - `life_indicators_query`: 生命指示器查询
+/// - `config`: 游戏配置，生命指示器使用小球颜色
 pub fn update_lives_ui(
     mut commands: Commands,
     lives: Res<Lives>,
+    config: Res<GameConfig>,
     lives_ui_query: Query<Entity, With<LivesUi>>,
     life_indicators_query: Query<Entity, With<LifeIndicator>>,
 ) {
@@ -601,7 +1221,7 @@ pub fn update_lives_ui(
                             border_radius: BorderRadius::all(Val::Px(LIFE_INDICATOR_SIZE / 2.0)),
                             ..default()
                         },
-                        BackgroundColor(BALL_COLOR),
+                        BackgroundColor(config.ball_color),
                         LifeIndicator,
                     ));
                 }
@@ -615,11 +1235,12 @@ pub fn update_lives_ui(
 /// # 参数
 /// - `commands`: 命令缓冲区
 /// - `location`: 墙壁位置
+/// - `config`: 游戏配置，提供墙壁位置、尺寸和颜色
 ///
 /// # 说明
 /// 创建墙壁实体并添加 GamePlayRoot 标记，便于在游戏结束时清理
-fn spawn_wall_with_marker(commands: &mut Commands, location: WallLocation) {
-    let (wall, location, sprite, transform) = Wall::new(location);
+fn spawn_wall_with_marker(commands: &mut Commands, location: WallLocation, config: &GameConfig) {
+    let (wall, location, sprite, transform) = Wall::new(location, config);
     commands.spawn((
         wall,
         location,
@@ -654,42 +1275,77 @@ pub fn cleanup_game_play(
 /// - `asset_server`: 资源服务器，用于加载图片资源
 /// - `atlas_layouts`: 纹理图集布局资源
 /// - `paddle_query`: 挡板查询
+/// - `ball_query`: 小球查询
+/// - `config`: 游戏配置，提供小球尺寸
 pub fn reset_ball(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     paddle_query: Query<&Transform, With<Paddle>>,
+    ball_query: Query<(), With<Ball>>,
+    config: Res<GameConfig>,
 ) {
+    // 场上还有小球时（例如粘性挡板粘住的小球）不再生成新的小球
+    if !ball_query.is_empty() {
+        return;
+    }
     if let Ok(paddle_transform) = paddle_query.single() {
-        let paddle_y = paddle_transform.translation.y;
-        let ball_y = paddle_y + PADDLE_SIZE.y / 2.0 + BALL_DIAMETER / 2.0 + 5.0;
-
-        // 加载精灵图集纹理 (256x64，4列，每帧64x64)
-        let ball_texture = asset_server.load("images/sprite (1).png");
-        // 创建纹理图集布局：4列1行，每个精灵64x64
-        let atlas_layout = TextureAtlasLayout::from_grid(UVec2::new(64, 64), 4, 1, None, None);
-        let atlas_layout_handle = atlas_layouts.add(atlas_layout);
+        let ball_y = paddle_transform.translation.y + config.attached_ball_height();
 
         // 生成新的小球，附着在挡板上
         commands.spawn((
-            Sprite::from_atlas_image(
-                ball_texture,
-                TextureAtlas {
-                    layout: atlas_layout_handle,
-                    index: 0,
-                },
+            ball_bundle(
+                &asset_server,
+                &mut atlas_layouts,
+                &config,
+                Vec3::new(paddle_transform.translation.x, ball_y, 1.0),
             ),
-            Transform::from_translation(Vec3::new(paddle_transform.translation.x, ball_y, 1.0))
-                .with_scale(Vec2::splat(BALL_DIAMETER / 64.0).extend(1.)),
-            Ball,
             BallAttached,
-            // 添加动画组件：4帧，每帧0.15秒
-            BallAnimation::new(4, 0.15),
-            GamePlayRoot,
         ));
     }
 }
 
+/// 创建小球的组件包（不含速度和附着状态）
+///
+/// # 参数
+/// - `asset_server`: 资源服务器，用于加载小球的精灵图集
+/// - `atlas_layouts`: 纹理图集布局资源
+/// - `config`: 游戏配置，提供小球直径
+/// - `translation`: 小球的初始位置
+///
+/// # 说明
+/// 开局、失去生命后重置以及多球道具都通过它生成小球，保证外观和动画一致
+pub fn ball_bundle(
+    asset_server: &AssetServer,
+    atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    config: &GameConfig,
+    translation: Vec3,
+) -> impl Bundle {
+    // 加载精灵图集纹理 (256x64，4列，每帧64x64)
+    let ball_texture = asset_server.load("images/sprite (1).png");
+    // 创建纹理图集布局：4列1行，每个精灵64x64
+    let atlas_layout = TextureAtlasLayout::from_grid(UVec2::new(64, 64), 4, 1, None, None);
+    let atlas_layout_handle = atlas_layouts.add(atlas_layout);
+
+    (
+        // 使用精灵图集，显示第0帧（第一列）
+        Sprite::from_atlas_image(
+            ball_texture,
+            TextureAtlas {
+                layout: atlas_layout_handle,
+                index: 0,
+            },
+        ),
+        // 设置缩放使图片大小符合配置中的小球直径
+        Transform::from_translation(translation)
+            .with_scale(Vec2::splat(config.ball_diameter / 64.0).extend(1.)),
+        Ball,
+        // 添加动画组件：4帧，每帧0.15秒
+        BallAnimation::new(4, 0.15),
+        GamePlayRoot,
+    )
+}
+
 /// 小球动画系统
 /// 循环切换精灵图集的帧，实现动画效果
 ///
@@ -719,3 +1375,120 @@ pub fn animate_ball_sprite(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以 (0, 0) 为中心、宽 100 高 20 的矩形
+    fn brick_box() -> Aabb2d {
+        Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 10.0))
+    }
+
+    fn ball_at(x: f32, y: f32) -> BoundingCircle {
+        BoundingCircle::new(Vec2::new(x, y), 5.0)
+    }
+
+    #[test]
+    fn ball_collision_misses_separated_box() {
+        assert_eq!(ball_collision(ball_at(0.0, 20.0), brick_box()), None);
+        assert_eq!(ball_collision(ball_at(60.0, 0.0), brick_box()), None);
+        // 角落外侧：两个轴都在半径范围内，但离角点的距离超过半径
+        assert_eq!(ball_collision(ball_at(54.0, 14.0), brick_box()), None);
+    }
+
+    #[test]
+    fn ball_collision_reports_touched_side() {
+        assert_eq!(ball_collision(ball_at(0.0, 14.0), brick_box()), Some(Collision::Top));
+        assert_eq!(ball_collision(ball_at(0.0, -14.0), brick_box()), Some(Collision::Bottom));
+        assert_eq!(ball_collision(ball_at(-54.0, 0.0), brick_box()), Some(Collision::Left));
+        assert_eq!(ball_collision(ball_at(54.0, 0.0), brick_box()), Some(Collision::Right));
+    }
+
+    #[test]
+    fn ball_collision_with_center_inside_uses_nearest_side() {
+        assert_eq!(ball_collision(ball_at(0.0, 8.0), brick_box()), Some(Collision::Top));
+        assert_eq!(ball_collision(ball_at(0.0, -8.0), brick_box()), Some(Collision::Bottom));
+        assert_eq!(ball_collision(ball_at(-48.0, 0.0), brick_box()), Some(Collision::Left));
+        assert_eq!(ball_collision(ball_at(48.0, 0.0), brick_box()), Some(Collision::Right));
+    }
+
+    #[test]
+    fn depenetration_pushes_ball_to_contact() {
+        // 球心在矩形外，嵌入 2 个单位
+        let push = depenetration(ball_at(0.0, 13.0), brick_box(), Collision::Top);
+        assert!(push.abs_diff_eq(Vec2::new(0.0, 2.0), 1e-5));
+
+        // 球心在矩形内，沿碰撞边推出到接触位置
+        let push = depenetration(ball_at(0.0, 8.0), brick_box(), Collision::Top);
+        assert!(push.abs_diff_eq(Vec2::new(0.0, 7.0), 1e-5));
+
+        // 没有重叠时不需要推出
+        assert_eq!(depenetration(ball_at(0.0, 30.0), brick_box(), Collision::Top), Vec2::ZERO);
+    }
+
+    #[test]
+    fn ball_sweep_finds_time_of_impact() {
+        // 小球从下方垂直向上移动 100，在 y = -15 处接触矩形底部
+        let hit = ball_sweep(ball_at(0.0, -60.0), Vec2::new(0.0, 100.0), brick_box()).unwrap();
+        assert_eq!(hit.side, Collision::Bottom);
+        assert!((hit.time - 0.45).abs() < 1e-5);
+        assert_eq!(hit.push, Vec2::ZERO);
+    }
+
+    #[test]
+    fn ball_sweep_does_not_tunnel_through_thin_box() {
+        // 一步的位移远大于矩形厚度，离散检测的起点和终点都不重叠
+        let thin = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 2.0));
+        assert_eq!(ball_collision(ball_at(0.0, -40.0), thin), None);
+        assert_eq!(ball_collision(ball_at(0.0, 40.0), thin), None);
+
+        let hit = ball_sweep(ball_at(0.0, -40.0), Vec2::new(0.0, 80.0), thin).unwrap();
+        assert_eq!(hit.side, Collision::Bottom);
+        assert!((hit.time - 33.0 / 80.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ball_sweep_handles_corners() {
+        // 斜向经过角落外侧，没有碰到圆角
+        assert_eq!(ball_sweep(ball_at(45.0, 30.0), Vec2::new(30.0, -30.0), brick_box()), None);
+        // 朝角点移动，碰到圆角
+        let hit = ball_sweep(ball_at(70.0, 30.0), Vec2::new(-20.0, -20.0), brick_box()).unwrap();
+        assert!(hit.time > 0.0 && hit.time < 1.0);
+    }
+
+    #[test]
+    fn ball_sweep_ignores_overlap_when_moving_away() {
+        let ball = ball_at(0.0, 13.0);
+        assert_eq!(ball_sweep(ball, Vec2::new(0.0, 10.0), brick_box()), None);
+
+        let hit = ball_sweep(ball, Vec2::new(0.0, -10.0), brick_box()).unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.side, Collision::Top);
+        assert!(hit.push.abs_diff_eq(Vec2::new(0.0, 2.0), 1e-5));
+    }
+
+    #[test]
+    fn nearest_contact_picks_single_brick_on_seam() {
+        // 两块相邻的砖块，小球正好打在缝隙正下方
+        let left = Aabb2d::new(Vec2::new(-51.0, 0.0), Vec2::new(50.0, 10.0));
+        let right = Aabb2d::new(Vec2::new(51.0, 0.0), Vec2::new(50.0, 10.0));
+        let ball = ball_at(-0.5, -30.0);
+
+        let (id, hit) = nearest_contact(ball, Vec2::new(0.0, 40.0), [("left", left), ("right", right)]).unwrap();
+        assert_eq!(id, "left");
+        assert_eq!(hit.side, Collision::Bottom);
+    }
+
+    #[test]
+    fn nearest_contact_picks_earliest_collider() {
+        let near = Aabb2d::new(Vec2::new(0.0, 0.0), Vec2::new(50.0, 10.0));
+        let far = Aabb2d::new(Vec2::new(0.0, 40.0), Vec2::new(50.0, 10.0));
+        let ball = ball_at(0.0, -30.0);
+
+        let (id, hit) = nearest_contact(ball, Vec2::new(0.0, 100.0), [(1, far), (0, near)]).unwrap();
+        assert_eq!(id, 0);
+        assert!((hit.time - 0.15).abs() < 1e-5);
+        assert_eq!(nearest_contact(ball, Vec2::new(0.0, -100.0), [(1, far), (0, near)]), None);
+    }
+}
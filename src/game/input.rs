@@ -0,0 +1,240 @@
+// 玩家输入：每个固定时间步长采样一次，游戏逻辑只读取采样结果
+
+use std::mem;
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use crate::actions::{Action, KeyBindings};
+use crate::components::Paddle;
+use crate::config::GameConfig;
+use crate::i18n::Language;
+use super::replay::{ReplayPlayback, ReplayRecorder};
+
+/// 控制挡板的输入设备
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum InputSource {
+    /// 键盘：按 [`KeyBindings`] 中绑定的按键移动和发射（默认为左右方向键移动，上方向键发射）
+    #[default]
+    Keyboard,
+    /// 手柄：左摇杆或十字键移动，South 键（Xbox 的 A 键）发射
+    Gamepad,
+    /// 鼠标或触摸：挡板跟随指针，单击或触摸发射
+    Mouse,
+}
+
+impl InputSource {
+    /// 所有输入设备，设置界面按这个顺序切换
+    pub const ALL: [InputSource; 3] = [InputSource::Keyboard, InputSource::Gamepad, InputSource::Mouse];
+
+    /// 输入设备名称（中文原文，显示时通过 [`Language::tr`] 翻译）
+    pub fn label(self) -> &'static str {
+        match self {
+            InputSource::Keyboard => "键盘",
+            InputSource::Gamepad => "手柄",
+            InputSource::Mouse => "鼠标",
+        }
+    }
+
+    /// 发射小球的操作提示
+    pub fn launch_hint(self, bindings: &KeyBindings, language: Language) -> String {
+        match self {
+            InputSource::Keyboard => language
+                .tr("按 {} 键发射小球")
+                .replace("{}", &bindings.label(Action::Launch, language)),
+            InputSource::Gamepad => language.tr("按手柄 A 键发射小球").to_string(),
+            InputSource::Mouse => language.tr("点击鼠标发射小球").to_string(),
+        }
+    }
+}
+
+/// 输入设置资源
+///
+/// # 说明
+/// 默认使用键盘，由 [`SettingsPlugin`](crate::SettingsPlugin) 从设置文件读取并在修改后保存
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSettings {
+    /// 控制挡板的输入设备
+    pub source: InputSource,
+    /// 鼠标跟随灵敏度：挡板追赶指针的最大速度是挡板速度的多少倍
+    pub mouse_sensitivity: f32,
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self {
+            source: InputSource::Keyboard,
+            mouse_sensitivity: 1.5,
+        }
+    }
+}
+
+/// 玩家输入资源，记录当前固定时间步长内玩家的操作
+///
+/// # 说明
+/// 挡板移动、发射小球和发射激光都只读取这个资源，不直接读取键盘、手柄或鼠标，
+/// 因此录像回放时只需要替换这个资源的内容
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    /// 挡板移动方向和速度比例：负数向左，正数向右，1.0 为挡板的最大速度
+    ///
+    /// 键盘为 -1、0、1；手柄摇杆为中间值；鼠标跟随时最大为鼠标灵敏度
+    pub direction: f32,
+    /// 是否按下了发射键（发射小球或激光）
+    pub fire: bool,
+}
+
+/// 发射键锁存资源
+///
+/// 按键的“刚按下”只在一帧内有效，而一帧内可能运行零个或多个固定时间步长。
+/// 按下时先锁存，由下一个固定时间步长取走，保证每次按键恰好生效一次
+#[derive(Resource, Debug, Default)]
+pub struct FireLatch(pub bool);
+
+/// 指针位置资源：鼠标光标或触摸点在游戏世界中的横坐标
+///
+/// 光标离开窗口且没有触摸时为 None，此时挡板停在原地
+#[derive(Resource, Debug, Default)]
+pub struct PointerTarget(pub Option<f32>);
+
+/// 锁存发射键系统（每帧在输入更新后运行）
+///
+/// # 参数
+/// - `settings`: 输入设置，只响应当前输入设备的发射键
+/// - `keyboard_input`: 键盘输入资源
+/// - `bindings`: 按键绑定
+/// - `mouse_input`: 鼠标按键输入资源
+/// - `touches`: 触摸输入资源
+/// - `gamepads`: 已连接的手柄
+/// - `fire_latch`: 发射键锁存资源
+pub fn latch_fire_input(
+    settings: Res<InputSettings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    mut fire_latch: ResMut<FireLatch>,
+) {
+    let fire = match settings.source {
+        InputSource::Keyboard => bindings.just_pressed(Action::Launch, &keyboard_input),
+        InputSource::Gamepad => gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South)),
+        InputSource::Mouse => {
+            mouse_input.just_pressed(MouseButton::Left) || touches.any_just_pressed()
+        }
+    };
+    if fire {
+        fire_latch.0 = true;
+    }
+}
+
+/// 更新指针位置系统（每帧在输入更新后运行）
+///
+/// # 参数
+/// - `window`: 主窗口，用于读取光标位置
+/// - `camera`: 相机，用于把窗口坐标转换为游戏世界坐标
+/// - `touches`: 触摸输入资源，有触摸时优先跟随触摸点
+/// - `pointer_target`: 指针位置资源
+///
+/// # 说明
+/// 没有窗口或相机时（例如无窗口运行）不修改指针位置
+pub fn track_pointer(
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    camera: Option<Single<(&Camera, &GlobalTransform)>>,
+    touches: Res<Touches>,
+    mut pointer_target: ResMut<PointerTarget>,
+) {
+    let (Some(window), Some(camera)) = (window, camera) else {
+        return;
+    };
+    let (camera, camera_transform) = *camera;
+    pointer_target.0 = touches
+        .first_pressed_position()
+        .or_else(|| window.cursor_position())
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position).ok())
+        .map(|position| position.x);
+}
+
+/// 采样玩家输入系统（每个固定时间步长开始时运行）
+///
+/// # 参数
+/// - `settings`: 输入设置，决定从哪个输入设备采样
+/// - `keyboard_input`: 键盘输入资源
+/// - `bindings`: 按键绑定
+/// - `gamepads`: 已连接的手柄
+/// - `pointer_target`: 指针位置资源
+/// - `paddle_transform`: 挡板变换组件，鼠标跟随时计算挡板与指针的距离
+/// - `time`: 时间资源（固定时间步长）
+/// - `config`: 游戏配置，提供挡板速度
+/// - `fire_latch`: 发射键锁存资源
+/// - `playback`: 正在回放的录像，存在时使用录像中的输入代替玩家输入
+/// - `recorder`: 录像记录器，记录本步长的输入
+/// - `player_input`: 玩家输入资源
+///
+/// # 说明
+/// 鼠标跟随在这里换算为移动方向：挡板在本步长内正好追上指针所需的速度比例，
+/// 最大不超过鼠标灵敏度。录像只记录换算后的方向，回放结果不受回放时的设置影响
+pub fn sample_player_input(
+    settings: Res<InputSettings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    pointer_target: Res<PointerTarget>,
+    paddle_transform: Option<Single<&Transform, With<Paddle>>>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut fire_latch: ResMut<FireLatch>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    // 回放时也要取走锁存，避免回放结束后触发回放期间的按键
+    let fire = mem::take(&mut fire_latch.0);
+    *player_input = match playback {
+        Some(mut playback) => playback.next_input(),
+        None => {
+            let direction = match settings.source {
+                InputSource::Keyboard => {
+                    let mut direction = 0.0;
+                    // 检测向左/向右移动键输入 pressed持续按下
+                    if bindings.pressed(Action::MoveLeft, &keyboard_input) {
+                        direction -= 1.0;
+                    }
+                    if bindings.pressed(Action::MoveRight, &keyboard_input) {
+                        direction += 1.0;
+                    }
+                    direction
+                }
+                // 左摇杆和十字键取偏移较大的一个，多个手柄时使用第一个有输入的手柄
+                InputSource::Gamepad => gamepads
+                    .iter()
+                    .map(|gamepad| {
+                        let stick = gamepad.left_stick().x;
+                        let dpad = gamepad.dpad().x;
+                        if dpad.abs() > stick.abs() { dpad } else { stick }
+                    })
+                    .find(|direction| *direction != 0.0)
+                    .unwrap_or(0.0)
+                    .clamp(-1.0, 1.0),
+                InputSource::Mouse => match (pointer_target.0, paddle_transform) {
+                    (Some(target_x), Some(paddle_transform)) => {
+                        let max_step = config.paddle_speed * time.delta_secs();
+                        let sensitivity = settings.mouse_sensitivity.max(0.0);
+                        if max_step > 0.0 {
+                            ((target_x - paddle_transform.translation.x) / max_step)
+                                .clamp(-sensitivity, sensitivity)
+                        } else {
+                            0.0
+                        }
+                    }
+                    _ => 0.0,
+                },
+            };
+            PlayerInput { direction, fire }
+        }
+    };
+    recorder.record(*player_input);
+}
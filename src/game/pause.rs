@@ -0,0 +1,184 @@
+// 暂停模块：暂停/继续游戏、冻结时间和暂停菜单
+
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use crate::actions::{Action, KeyBindings};
+use crate::i18n::Language;
+use crate::widgets::{self, UiTheme};
+use crate::state::*;
+use crate::components::*;
+use super::run::retry_level;
+
+/// 暂停菜单遮罩颜色（比其他遮罩更透明，仍能看到暂停时的画面）
+const PAUSE_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// 暂停菜单插件
+/// 注册暂停菜单的显示、交互和清理系统
+///
+/// # 说明
+/// 暂停状态的切换和时间冻结属于游戏逻辑，由 [`GameplayPlugin`](crate::game::GameplayPlugin) 注册，
+/// 因此无窗口运行时也可以暂停
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PauseState::Paused), setup_pause_menu)
+            .add_systems(Update, handle_pause_menu_input.run_if(in_state(PauseState::Paused)))
+            .add_systems(OnExit(PauseState::Paused), cleanup_pause_menu);
+    }
+}
+
+/// 切换暂停状态系统（按暂停键，默认为 Esc 或 P 键，或者手柄的 Start 键）
+///
+/// # 参数
+/// - `keyboard_input`: 键盘输入资源
+/// - `bindings`: 按键绑定
+/// - `gamepads`: 已连接的手柄
+/// - `pause_state`: 当前暂停状态
+/// - `next_pause_state`: 下一个暂停状态
+pub fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    let start_pressed = gamepads.iter().any(|gamepad| gamepad.just_pressed(GamepadButton::Start));
+    if bindings.just_pressed(Action::Pause, &keyboard_input) || start_pressed {
+        next_pause_state.set(match pause_state.get() {
+            PauseState::Running => PauseState::Paused,
+            PauseState::Paused => PauseState::Running,
+        });
+    }
+}
+
+/// 窗口失去焦点时自动暂停系统
+///
+/// # 参数
+/// - `focus_events`: 窗口焦点变化消息
+/// - `next_pause_state`: 下一个暂停状态
+///
+/// # 说明
+/// 玩家切换到其他窗口时游戏自动暂停，重新获得焦点后不会自动继续，需要玩家手动继续
+pub fn pause_on_focus_loss(
+    mut focus_events: MessageReader<WindowFocused>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if focus_events.read().any(|event| !event.focused) {
+        next_pause_state.set(PauseState::Paused);
+    }
+}
+
+/// 冻结虚拟时间系统（进入暂停状态时调用）
+///
+/// # 参数
+/// - `time`: 虚拟时间资源
+///
+/// # 说明
+/// 虚拟时间冻结后 `FixedUpdate` 不再运行，道具计时和小球动画也会停止
+pub fn freeze_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// 恢复虚拟时间系统（离开暂停状态时调用，包括暂停中直接退出游戏）
+///
+/// # 参数
+/// - `time`: 虚拟时间资源
+pub fn unfreeze_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// 设置暂停菜单系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于创建UI实体
+/// - `theme`: 界面主题
+/// - `language`: 界面语言
+///
+/// # 功能
+/// 创建暂停菜单，包含标题和继续游戏、重新开始、返回菜单三个按钮
+pub fn setup_pause_menu(mut commands: Commands, theme: Res<UiTheme>, language: Res<Language>) {
+    let buttons = [
+        (PauseMenuButton::Resume, "继续游戏"),
+        (PauseMenuButton::RestartLevel, "重新开始"),
+        (PauseMenuButton::QuitToMenu, "返回菜单"),
+    ];
+
+    commands
+        .spawn((
+            widgets::screen(PAUSE_BACKGROUND),
+            // 显示在游戏内UI（记分板、生命数）之上
+            GlobalZIndex(1),
+            PauseMenuRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn(widgets::title(&theme, language.tr("暂停"), Color::WHITE));
+            for (action, label) in buttons {
+                parent.spawn((
+                    widgets::button(&theme, language.tr(label), theme.overlay_button, theme.button),
+                    action,
+                ));
+            }
+        });
+}
+
+/// 处理暂停菜单按钮点击系统
+///
+/// # 参数
+/// - `interaction_query`: 查询按钮的交互状态和按钮操作
+/// - `score`: 分数资源，重新开始时恢复为进入这一关时的分数
+/// - `lives`: 生命数资源，重新开始时恢复为本局难度的初始生命数
+/// - `run`: 本局游戏进度
+/// - `next_game_state`: 下一个游戏状态
+/// - `next_playing_state`: 下一个游戏进行中状态
+/// - `next_pause_state`: 下一个暂停状态
+///
+/// # 功能
+/// - 继续游戏：回到 Running 状态
+/// - 重新开始：重新进入 Playing 状态（OnExit/OnEnter 会清理并重新生成关卡）
+/// - 返回菜单：切换到菜单状态，暂停子状态随之移除
+pub fn handle_pause_menu_input(
+    interaction_query: Query<(&Interaction, &PauseMenuButton), Changed<Interaction>>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    run: Res<RunProgress>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_playing_state: ResMut<NextState<PlayingState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            PauseMenuButton::Resume => next_pause_state.set(PauseState::Running),
+            PauseMenuButton::RestartLevel => {
+                // set 对相同状态也会触发 OnExit/OnEnter
+                retry_level(
+                    &mut score,
+                    &mut lives,
+                    &run,
+                    &mut next_game_state,
+                    &mut next_playing_state,
+                );
+                // 父状态重新进入自身时子状态保持不变，需要手动回到 Running
+                next_pause_state.set(PauseState::Running);
+            }
+            PauseMenuButton::QuitToMenu => next_game_state.set(GameState::Menu),
+        }
+    }
+}
+
+/// 清理暂停菜单系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `pause_menu_entities`: 查询所有带有 PauseMenuRoot 标记的实体
+pub fn cleanup_pause_menu(
+    mut commands: Commands,
+    pause_menu_entities: Query<Entity, With<PauseMenuRoot>>,
+) {
+    for entity in &pause_menu_entities {
+        commands.entity(entity).despawn();
+    }
+}
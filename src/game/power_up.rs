@@ -0,0 +1,384 @@
+// 道具系统：掉落、拾取、限时效果和激光
+
+use bevy::ecs::entity::EntityHashSet;
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use crate::config::*;
+use crate::components::*;
+use crate::i18n::Language;
+use crate::state::GameState;
+use crate::widgets::{self, UiTheme};
+use super::game1::{ColliderQuery, ball_bundle, damage_brick};
+use super::input::PlayerInput;
+
+/// 按概率在指定位置掉落一个随机道具
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `rng`: 随机数生成器
+/// - `position`: 掉落位置（被摧毁砖块的中心）
+pub fn try_drop_power_up(commands: &mut Commands, rng: &mut GameRng, position: Vec2) {
+    if !rng.random_bool(POWER_UP_DROP_CHANCE) {
+        return;
+    }
+    let Some(&kind) = PowerUpKind::DROPPABLE.choose(&mut rng.0) else {
+        return;
+    };
+
+    // 道具胶囊：彩色矩形 + 字母标识，带有向下的速度，由 apply_velocity 系统负责下落
+    // 使用 custom_size 而不是 scale 设置大小，避免子实体的文字被拉伸
+    commands.spawn((
+        Sprite {
+            color: kind.color(),
+            custom_size: Some(POWER_UP_SIZE),
+            ..default()
+        },
+        Transform::from_translation(position.extend(2.0)),
+        PowerUp(kind),
+        Velocity(Vec2::new(0.0, -POWER_UP_FALL_SPEED)),
+        GamePlayRoot,
+        children![(
+            Text2d::new(kind.label()),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Transform::from_xyz(0.0, 0.0, 0.1),
+        )],
+    ));
+}
+
+/// 拾取道具系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `asset_server`: 资源服务器，多球道具生成新小球时使用
+/// - `atlas_layouts`: 纹理图集布局资源
+/// - `power_up_query`: 道具胶囊查询
+/// - `paddle_transform`: 挡板变换组件
+/// - `ball_query`: 运动中的小球查询
+/// - `active_power_ups`: 当前生效的道具
+/// - `lives`: 生命数资源，额外生命道具会增加生命数
+/// - `config`: 游戏配置，提供底部墙壁位置和小球尺寸
+///
+/// # 逻辑
+/// - 胶囊碰到挡板：道具生效，胶囊消失
+/// - 胶囊落出底部墙壁：直接消失
+pub fn collect_power_ups(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    power_up_query: Query<(Entity, &Transform, &PowerUp)>,
+    paddle_transform: Single<&Transform, With<Paddle>>,
+    ball_query: Query<(&Transform, &Velocity), (With<Ball>, Without<BallAttached>)>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut lives: ResMut<Lives>,
+    config: Res<GameConfig>,
+) {
+    let paddle_box = Aabb2d::new(
+        paddle_transform.translation.truncate(),
+        paddle_transform.scale.truncate() / 2.,
+    );
+
+    for (entity, transform, power_up) in &power_up_query {
+        let position = transform.translation.truncate();
+        if Aabb2d::new(position, POWER_UP_SIZE / 2.).intersects(&paddle_box) {
+            commands.entity(entity).despawn();
+            match power_up.0.duration() {
+                // 限时道具：加入生效列表，由其他系统根据列表调整挡板和小球
+                Some(duration) => active_power_ups.activate(power_up.0, duration),
+                // 立即生效的道具
+                None => match power_up.0 {
+                    PowerUpKind::MultiBall => split_balls(
+                        &mut commands,
+                        &asset_server,
+                        &mut atlas_layouts,
+                        &config,
+                        &paddle_transform,
+                        &ball_query,
+                    ),
+                    PowerUpKind::ExtraLife => lives.0 += 1,
+                    _ => {}
+                },
+            }
+        } else if position.y < config.bottom_wall {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 多球道具：每个运动中的小球分裂出 MULTI_BALL_SPLIT 个新小球
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `asset_server`: 资源服务器
+/// - `atlas_layouts`: 纹理图集布局资源
+/// - `config`: 游戏配置，提供小球尺寸
+/// - `paddle_transform`: 挡板变换组件
+/// - `ball_query`: 运动中的小球查询
+///
+/// # 说明
+/// - 新小球与原小球位置相同，速度方向依次偏转 +MULTI_BALL_SPREAD、-MULTI_BALL_SPREAD、+2×MULTI_BALL_SPREAD……
+/// - 没有运动中的小球时（小球都附着在挡板上），新小球同样附着在挡板上，发射时由 handle_ball_launch 散开
+fn split_balls(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    config: &GameConfig,
+    paddle_transform: &Transform,
+    ball_query: &Query<(&Transform, &Velocity), (With<Ball>, Without<BallAttached>)>,
+) {
+    if ball_query.is_empty() {
+        // 位置由 move_attached_ball 系统每帧同步到挡板上方
+        for _ in 0..MULTI_BALL_SPLIT {
+            commands.spawn((
+                ball_bundle(asset_server, atlas_layouts, config, paddle_transform.translation.with_z(1.0)),
+                BallAttached,
+            ));
+        }
+        return;
+    }
+
+    for (transform, velocity) in ball_query {
+        for index in 0..MULTI_BALL_SPLIT {
+            // 左右交替偏转：+1, -1, +2, -2 ...
+            let step = (index / 2 + 1) as f32;
+            let sign = if index % 2 == 0 { 1.0 } else { -1.0 };
+            let rotation = Vec2::from_angle(sign * step * MULTI_BALL_SPREAD);
+            commands.spawn((
+                ball_bundle(asset_server, atlas_layouts, config, transform.translation),
+                Velocity(rotation.rotate(velocity.0)),
+            ));
+        }
+    }
+}
+
+/// 推进道具计时器系统，移除到期的道具
+///
+/// # 参数
+/// - `time`: 时间资源
+/// - `active_power_ups`: 当前生效的道具
+pub fn tick_power_ups(time: Res<Time>, mut active_power_ups: ResMut<ActivePowerUps>) {
+    active_power_ups.tick(time.delta());
+}
+
+/// 清除道具效果系统（进入游戏或失去一条生命时调用）
+///
+/// # 参数
+/// - `active_power_ups`: 当前生效的道具
+pub fn clear_power_ups(mut active_power_ups: ResMut<ActivePowerUps>) {
+    active_power_ups.clear();
+}
+
+/// 更新挡板宽度系统
+///
+/// # 参数
+/// - `active_power_ups`: 当前生效的道具
+/// - `paddle_transform`: 挡板变换组件
+/// - `config`: 游戏配置，提供挡板原始宽度
+/// - `run`: 本局游戏进度，难度决定挡板宽度的倍数
+///
+/// # 说明
+/// 每帧根据配置中的挡板尺寸、难度和生效的道具重新计算挡板宽度，道具到期后自动恢复原宽度
+pub fn update_paddle_size(
+    active_power_ups: Res<ActivePowerUps>,
+    mut paddle_transform: Single<&mut Transform, With<Paddle>>,
+    config: Res<GameConfig>,
+    run: Res<RunProgress>,
+) {
+    let base_width = config.paddle_size.x * run.difficulty.paddle_width_scale();
+    let width = if active_power_ups.is_active(PowerUpKind::WidePaddle) {
+        base_width * WIDE_PADDLE_SCALE
+    } else {
+        base_width
+    };
+    if paddle_transform.scale.x != width {
+        paddle_transform.scale.x = width;
+    }
+}
+
+/// 同步小球速度系统
+///
+/// # 参数
+/// - `active_power_ups`: 当前生效的道具
+/// - `ball_query`: 运动中的小球速度查询
+/// - `config`: 游戏配置，提供小球原始速度
+/// - `run`: 本局游戏进度，难度决定小球初始速度和加速上限
+/// - `progression`: 小球加速资源
+///
+/// # 说明
+/// 保持小球方向不变，将速度大小设为配置中的小球速度 × 难度倍数 × 加速倍数
+/// （减速道具生效时再乘以 SLOW_BALL_SCALE），道具到期后小球自动恢复原速度
+pub fn sync_ball_speed(
+    active_power_ups: Res<ActivePowerUps>,
+    mut ball_query: Query<&mut Velocity, (With<Ball>, Without<BallAttached>)>,
+    config: Res<GameConfig>,
+    run: Res<RunProgress>,
+    progression: Res<SpeedProgression>,
+) {
+    let base_speed = config.ball_speed * run.difficulty.ball_speed_scale() * progression.scale(run.difficulty);
+    let speed = if active_power_ups.is_active(PowerUpKind::SlowBall) {
+        base_speed * SLOW_BALL_SCALE
+    } else {
+        base_speed
+    };
+    for mut velocity in &mut ball_query {
+        if let Some(direction) = velocity.try_normalize() {
+            velocity.0 = direction * speed;
+        }
+    }
+}
+
+/// 发射激光系统（激光道具生效时按发射键发射）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `player_input`: 本固定时间步长的玩家输入
+/// - `active_power_ups`: 当前生效的道具
+/// - `paddle_transform`: 挡板变换组件，激光从挡板两端发射
+pub fn fire_lasers(
+    mut commands: Commands,
+    player_input: Res<PlayerInput>,
+    active_power_ups: Res<ActivePowerUps>,
+    paddle_transform: Single<&Transform, With<Paddle>>,
+) {
+    if !active_power_ups.is_active(PowerUpKind::Laser) || !player_input.fire {
+        return;
+    }
+
+    // 激光生成在挡板上方 1 像素处，避免一出现就与挡板相交
+    let paddle_top = paddle_transform.translation.y + paddle_transform.scale.y / 2.0 + 1.0;
+    let half_width = paddle_transform.scale.x / 2.0 - LASER_SIZE.x;
+    for offset in [-half_width, half_width] {
+        commands.spawn((
+            Sprite::from_color(LASER_COLOR, Vec2::ONE),
+            Transform {
+                translation: Vec3::new(
+                    paddle_transform.translation.x + offset,
+                    paddle_top + LASER_SIZE.y / 2.0,
+                    1.0,
+                ),
+                scale: LASER_SIZE.extend(1.0),
+                ..default()
+            },
+            LaserBolt,
+            Velocity(Vec2::new(0.0, LASER_SPEED)),
+            GamePlayRoot,
+        ));
+    }
+}
+
+/// 激光碰撞检测系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `remaining_bricks`: 剩余砖块数量，归零时通关
+/// - `rng`: 随机数生成器，用于决定砖块是否掉落道具
+/// - `laser_query`: 激光查询
+/// - `collider_query`: 碰撞器实体查询
+/// - `brick_health_query`: 砖块耐久查询
+/// - `next_game_state`: 下一个游戏状态
+/// - `config`: 游戏配置，提供砖块颜色和爆炸半径
+///
+/// # 逻辑
+/// 激光与小球一样对砖块造成一次伤害，击中任何碰撞体（砖块、墙壁）后消失
+pub fn laser_collisions(
+    mut commands: Commands,
+    mut remaining_bricks: ResMut<RemainingBricks>,
+    mut rng: ResMut<GameRng>,
+    laser_query: Query<(Entity, &Transform), With<LaserBolt>>,
+    collider_query: ColliderQuery,
+    mut brick_health_query: Query<(&mut BrickHealth, &mut Sprite)>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    config: Res<GameConfig>,
+) {
+    let mut destroyed_bricks = EntityHashSet::default();
+
+    for (laser_entity, laser_transform) in &laser_query {
+        let laser_box = Aabb2d::new(
+            laser_transform.translation.truncate(),
+            laser_transform.scale.truncate() / 2.,
+        );
+
+        // 激光只作用于第一个击中的碰撞体
+        let hit = collider_query.iter().find(|(entity, transform, _, _)| {
+            !destroyed_bricks.contains(entity)
+                && laser_box.intersects(&Aabb2d::new(
+                    transform.translation.truncate(),
+                    transform.scale.truncate() / 2.,
+                ))
+        });
+        let Some((collider_entity, _, maybe_brick, _)) = hit else {
+            continue;
+        };
+
+        commands.entity(laser_entity).despawn();
+        if let Some(&brick_kind) = maybe_brick
+            && damage_brick(
+                &mut commands,
+                &mut remaining_bricks,
+                &mut rng,
+                &mut destroyed_bricks,
+                &collider_query,
+                &mut brick_health_query,
+                &config,
+                collider_entity,
+                brick_kind,
+                None,
+            )
+            && remaining_bricks.0 == 0
+        {
+            commands.trigger(LevelCleared);
+            next_game_state.set(GameState::Victory);
+            return;
+        }
+    }
+}
+
+/// 生成道具状态栏
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `theme`: 界面主题，提供能显示中文道具名称的字体
+/// - `config`: 游戏配置，提供文字颜色
+pub fn spawn_power_up_hud(commands: &mut Commands, theme: &UiTheme, config: &GameConfig) {
+    commands.spawn((
+        widgets::label(theme, "", POWER_UP_HUD_FONT_SIZE, config.text_color),
+        Node {
+            position_type: PositionType::Absolute,
+            top: POWER_UP_HUD_TOP,
+            left: SCOREBOARD_TEXT_PADDING,
+            ..default()
+        },
+        PowerUpHudUi,
+        GamePlayRoot,
+    ));
+}
+
+/// 更新道具状态栏系统
+///
+/// # 参数
+/// - `active_power_ups`: 当前生效的道具
+/// - `hud_text`: 道具状态栏文本
+/// - `language`: 界面语言
+///
+/// # 说明
+/// 显示每个生效道具的名称和剩余秒数，例如 "加宽 8s  激光 3s"
+pub fn update_power_up_hud(
+    active_power_ups: Res<ActivePowerUps>,
+    mut hud_text: Single<&mut Text, With<PowerUpHudUi>>,
+    language: Res<Language>,
+) {
+    let text = active_power_ups
+        .effects
+        .iter()
+        .map(|(kind, timer)| format!("{} {}s", language.tr(kind.name()), timer.remaining_secs().ceil()))
+        .collect::<Vec<_>>()
+        .join("  ");
+    if hud_text.0 != text {
+        hud_text.0 = text;
+    }
+}
@@ -0,0 +1,208 @@
+// 录像：记录每个固定时间步长的玩家输入，回放时逐步喂回游戏逻辑
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use crate::components::{GameRng, Lives, RunProgress};
+use crate::config::GameConfig;
+use crate::levels::LevelCatalog;
+use crate::settings::Difficulty;
+use crate::state::Level;
+use super::input::PlayerInput;
+use super::snapshot::PendingResume;
+
+/// 一局游戏的录像
+///
+/// # 说明
+/// 物理和游戏逻辑全部在固定时间步长中运行，随机数种子、关卡、配置和每步的输入相同时，
+/// 回放与录制时的过程逐位一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// 关卡在关卡目录中的索引
+    pub level: usize,
+    /// 关卡名称，回放前用于确认关卡目录没有变化
+    pub level_name: String,
+    /// 录制时游戏配置的哈希值（见 [`config_hash`]），配置不同时回放结果会不同
+    pub config_hash: u64,
+    /// 随机数种子
+    pub seed: u64,
+    /// 开始时的生命数（由录制时的难度决定）
+    pub lives: usize,
+    /// 录制时的难度，回放时使用这个难度而不是设置中的难度（旧录像中没有这一项，视为普通难度）
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// 每个固定时间步长的输入，连续相同的输入合并为 (输入, 重复次数)
+    pub inputs: Vec<(PlayerInput, u32)>,
+}
+
+impl Replay {
+    /// 创建空录像
+    pub fn new(
+        level: usize,
+        level_name: String,
+        config_hash: u64,
+        seed: u64,
+        lives: usize,
+        difficulty: Difficulty,
+    ) -> Self {
+        Self {
+            level,
+            level_name,
+            config_hash,
+            seed,
+            lives,
+            difficulty,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// 追加一个固定时间步长的输入
+    pub fn push(&mut self, input: PlayerInput) {
+        match self.inputs.last_mut() {
+            Some((last, count)) if *last == input => *count += 1,
+            _ => self.inputs.push((input, 1)),
+        }
+    }
+
+    /// 录像包含的固定时间步长数量
+    pub fn ticks(&self) -> usize {
+        self.inputs.iter().map(|(_, count)| *count as usize).sum()
+    }
+}
+
+/// 录像记录器资源，正在录制时保存当前这局的录像
+///
+/// 从存档继续的游戏和回放中的游戏不录制
+#[derive(Resource, Debug, Default)]
+pub struct ReplayRecorder(pub Option<Replay>);
+
+impl ReplayRecorder {
+    /// 记录一个固定时间步长的输入（没有在录制时忽略）
+    pub fn record(&mut self, input: PlayerInput) {
+        if let Some(replay) = &mut self.0 {
+            replay.push(input);
+        }
+    }
+}
+
+/// 录像回放资源，存在时游戏使用录像中的输入代替玩家输入
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    /// 正在回放的录像
+    pub replay: Replay,
+    /// 当前所在的输入段
+    run: usize,
+    /// 当前输入段中已经回放的步数
+    offset: u32,
+    /// 已经回放的固定时间步长数量
+    tick: usize,
+}
+
+impl ReplayPlayback {
+    /// 从头开始回放录像
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            run: 0,
+            offset: 0,
+            tick: 0,
+        }
+    }
+
+    /// 取出下一个固定时间步长的输入，录像播放完后返回空输入
+    pub fn next_input(&mut self) -> PlayerInput {
+        let Some(&(input, count)) = self.replay.inputs.get(self.run) else {
+            return PlayerInput::default();
+        };
+        self.tick += 1;
+        self.offset += 1;
+        if self.offset >= count {
+            self.run += 1;
+            self.offset = 0;
+        }
+        input
+    }
+
+    /// 已经回放的固定时间步长数量
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// 录像是否已经播放完
+    pub fn is_finished(&self) -> bool {
+        self.run >= self.replay.inputs.len()
+    }
+}
+
+/// 计算游戏配置的哈希值
+///
+/// # 说明
+/// 对配置的 RON 文本做 64 位 FNV-1a 哈希，结果不依赖编译器版本和运行平台
+pub fn config_hash(config: &GameConfig) -> u64 {
+    let text = ron::to_string(config).unwrap_or_default();
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 开始录制或回放系统（进入游戏状态时，在 setup_game 之后、restore_run 之前运行）
+///
+/// # 参数
+/// - `recorder`: 录像记录器
+/// - `rng`: 随机数生成器，使用录像中的种子重新初始化
+/// - `playback`: 正在回放的录像
+/// - `pending_resume`: 等待恢复的存档，从存档继续的游戏不录制
+/// - `current_level`: 当前关卡资源
+/// - `catalog`: 关卡目录，用于获取关卡名称
+/// - `config`: 游戏配置
+/// - `lives`: 生命数资源，回放时恢复为录制时的初始生命数
+/// - `run`: 本局游戏进度，录制时记录其中的难度，回放时恢复为录制时的难度
+///
+/// # 说明
+/// 录制时生成新的随机数种子并记录在录像中，回放时使用录像中的种子，保证道具掉落一致；
+/// 难度影响小球速度、挡板宽度和反弹角度，同样记录在录像中
+pub fn start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut rng: ResMut<GameRng>,
+    playback: Option<Res<ReplayPlayback>>,
+    pending_resume: Option<Res<PendingResume>>,
+    current_level: Res<Level>,
+    catalog: Option<Res<LevelCatalog>>,
+    config: Res<GameConfig>,
+    mut lives: ResMut<Lives>,
+    mut run: ResMut<RunProgress>,
+) {
+    recorder.0 = None;
+    if let Some(playback) = playback {
+        rng.0 = StdRng::seed_from_u64(playback.replay.seed);
+        lives.0 = playback.replay.lives;
+        run.difficulty = playback.replay.difficulty;
+        return;
+    }
+    if pending_resume.is_some() {
+        return;
+    }
+    let Some(level) = catalog.as_ref().and_then(|catalog| catalog.get(current_level.0)) else {
+        return;
+    };
+
+    let seed = rng.random();
+    rng.0 = StdRng::seed_from_u64(seed);
+    recorder.0 = Some(Replay::new(
+        current_level.0,
+        level.name.clone(),
+        config_hash(&config),
+        seed,
+        lives.0,
+        run.difficulty,
+    ));
+}
+
+/// 结束回放系统（离开结束界面或回到菜单时调用）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+pub fn stop_playback(mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+}
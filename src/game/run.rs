@@ -0,0 +1,107 @@
+// 关卡流程：重试关卡、进入下一关和战役模式
+
+use bevy::prelude::*;
+use crate::components::*;
+use crate::levels::LevelCatalog;
+use crate::settings::Difficulty;
+use crate::state::{GameState, Level, PlayingState};
+
+/// 记录通关关卡系统（进入胜利状态时调用）
+///
+/// # 参数
+/// - `score`: 分数资源
+/// - `current_level`: 当前关卡资源
+/// - `catalog`: 关卡目录，用于记录关卡名称
+/// - `run`: 本局游戏进度
+///
+/// # 说明
+/// 通关时的总分减去之前关卡的得分即为这一关的得分
+pub fn record_cleared_level(
+    score: Res<Score>,
+    current_level: Res<Level>,
+    catalog: Option<Res<LevelCatalog>>,
+    mut run: ResMut<RunProgress>,
+) {
+    let name = catalog
+        .as_deref()
+        .and_then(|catalog| catalog.get(current_level.0))
+        .map(|level| level.name.clone())
+        .unwrap_or_default();
+    run.record_cleared(current_level.0, name, score.0);
+}
+
+/// 当前关卡之后是否还有关卡
+pub fn has_next_level(level: Level, catalog: Option<&LevelCatalog>) -> bool {
+    catalog.is_some_and(|catalog| level.0 + 1 < catalog.len())
+}
+
+/// 重新挑战当前关卡
+///
+/// # 参数
+/// - `score`: 分数资源，恢复为进入这一关时的分数
+/// - `lives`: 生命数资源，恢复为本局难度的初始生命数
+/// - `run`: 本局游戏进度
+/// - `next_game_state`: 下一个游戏状态
+/// - `next_playing_state`: 下一个游戏进行中状态
+///
+/// # 说明
+/// 重新进入 Playing 状态（从 Playing 重新进入时 OnExit/OnEnter 同样会清理并重新生成关卡）
+pub fn retry_level(
+    score: &mut Score,
+    lives: &mut Lives,
+    run: &RunProgress,
+    next_game_state: &mut NextState<GameState>,
+    next_playing_state: &mut NextState<PlayingState>,
+) {
+    score.0 = run.banked_score();
+    lives.0 = run.difficulty.starting_lives();
+    next_game_state.set(GameState::Playing);
+    next_playing_state.set(PlayingState::ball_attached);
+}
+
+/// 进入下一关，分数和剩余生命数保留
+///
+/// # 返回值
+/// 没有下一关时不切换状态并返回 false
+pub fn start_next_level(
+    level: &mut Level,
+    catalog: Option<&LevelCatalog>,
+    next_game_state: &mut NextState<GameState>,
+    next_playing_state: &mut NextState<PlayingState>,
+) -> bool {
+    if !has_next_level(*level, catalog) {
+        return false;
+    }
+    level.0 += 1;
+    next_game_state.set(GameState::Playing);
+    next_playing_state.set(PlayingState::ball_attached);
+    true
+}
+
+/// 开始战役模式：从第一关开始，使用全新的分数和生命数
+///
+/// # 参数
+/// - `score`: 分数资源
+/// - `lives`: 生命数资源
+/// - `difficulty`: 本局的难度
+/// - `level`: 当前关卡资源
+/// - `run`: 本局游戏进度
+/// - `next_game_state`: 下一个游戏状态
+/// - `next_playing_state`: 下一个游戏进行中状态
+pub fn start_campaign(
+    score: &mut Score,
+    lives: &mut Lives,
+    difficulty: Difficulty,
+    level: &mut Level,
+    run: &mut RunProgress,
+    next_game_state: &mut NextState<GameState>,
+    next_playing_state: &mut NextState<PlayingState>,
+) {
+    *run = RunProgress {
+        mode: GameMode::Campaign,
+        cleared: Vec::new(),
+        difficulty,
+    };
+    *level = Level(0);
+    retry_level(score, lives, run, next_game_state, next_playing_state);
+}
@@ -0,0 +1,241 @@
+// 游戏快照：记录进行中的一局游戏，并在继续游戏时重建场景
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::config::GameConfig;
+use crate::levels::LevelCatalog;
+use crate::state::{Level, PlayingState};
+use super::game1::{ball_bundle, spawn_brick};
+
+/// 一局游戏的快照
+///
+/// # 说明
+/// 记录重建场景所需的全部状态；下落中的道具胶囊和激光不会保存
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSnapshot {
+    /// 关卡在关卡目录中的索引
+    pub level: usize,
+    /// 关卡名称，继续游戏时用于确认关卡目录没有变化
+    pub level_name: String,
+    /// 分数
+    pub score: usize,
+    /// 剩余生命数
+    pub lives: usize,
+    /// 小球是否已经发射（对应 [`PlayingState`]）
+    pub ball_launched: bool,
+    /// 挡板位置
+    pub paddle: Vec2,
+    /// 场上的小球
+    pub balls: Vec<SavedBall>,
+    /// 剩余的砖块（包括钢铁砖块）
+    pub bricks: Vec<SavedBrick>,
+    /// 生效中的限时道具及其剩余时间（秒）
+    pub power_ups: Vec<(PowerUpKind, f32)>,
+    /// 游戏模式和本局已通关的关卡（旧存档中没有这一项，视为单关模式）
+    #[serde(default)]
+    pub run: RunProgress,
+    /// 连击数（旧存档中没有这一项，视为 0）
+    #[serde(default)]
+    pub combo: usize,
+    /// 本关已进行的时间（秒），用于计算通关用时奖励（旧存档中没有这一项，视为 0）
+    #[serde(default)]
+    pub elapsed_secs: f32,
+    /// 小球碰到挡板的次数，决定小球加速（旧存档中没有这一项，视为 0）
+    #[serde(default)]
+    pub paddle_hits: usize,
+}
+
+/// 快照中的小球
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedBall {
+    /// 小球位置
+    pub position: Vec2,
+    /// 小球速度，附着在挡板上的小球为 None
+    pub velocity: Option<Vec2>,
+}
+
+/// 快照中的砖块
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedBrick {
+    /// 砖块中心位置
+    pub position: Vec2,
+    /// 砖块类型
+    pub kind: BrickKind,
+    /// 剩余耐久，钢铁砖块为 None
+    pub health: Option<u8>,
+}
+
+impl RunSnapshot {
+    /// 快照对应的关卡是否仍在关卡目录中（索引和名称都一致）
+    pub fn matches(&self, catalog: &LevelCatalog) -> bool {
+        catalog
+            .get(self.level)
+            .is_some_and(|level| level.name == self.level_name)
+    }
+}
+
+/// 等待恢复的快照资源
+///
+/// 进入游戏状态前插入，[`setup_game`](super::game1::setup_game) 生成关卡后由 [`restore_run`] 按快照重建场景
+#[derive(Resource, Debug, Clone)]
+pub struct PendingResume(pub RunSnapshot);
+
+/// 记录当前进行中的一局游戏
+///
+/// # 参数
+/// - `world`: 游戏世界
+///
+/// # 返回值
+/// 不在游戏中（没有挡板）或关卡目录中找不到当前关卡时返回 None
+pub fn capture_run(world: &mut World) -> Option<RunSnapshot> {
+    let level = world.resource::<Level>().0;
+    let level_name = world.get_resource::<LevelCatalog>()?.get(level)?.name.clone();
+
+    let paddle = world
+        .query_filtered::<&Transform, With<Paddle>>()
+        .single(world)
+        .ok()?
+        .translation
+        .truncate();
+
+    let balls = world
+        .query_filtered::<(&Transform, Option<&Velocity>, Has<BallAttached>), With<Ball>>()
+        .iter(world)
+        .map(|(transform, velocity, attached)| SavedBall {
+            position: transform.translation.truncate(),
+            velocity: velocity.filter(|_| !attached).map(|velocity| velocity.0),
+        })
+        .collect();
+
+    let bricks = world
+        .query_filtered::<(&Transform, &BrickKind, Option<&BrickHealth>), With<Brick>>()
+        .iter(world)
+        .map(|(transform, kind, health)| SavedBrick {
+            position: transform.translation.truncate(),
+            kind: *kind,
+            health: health.map(|health| health.0),
+        })
+        .collect();
+
+    let power_ups = world
+        .resource::<ActivePowerUps>()
+        .effects
+        .iter()
+        .map(|(kind, timer)| (*kind, timer.remaining_secs()))
+        .collect();
+
+    Some(RunSnapshot {
+        level,
+        level_name,
+        score: world.resource::<Score>().0,
+        lives: world.resource::<Lives>().0,
+        ball_launched: *world.resource::<State<PlayingState>>().get() == PlayingState::ball_launched,
+        paddle,
+        balls,
+        bricks,
+        power_ups,
+        run: world.resource::<RunProgress>().clone(),
+        combo: world.resource::<Combo>().0,
+        elapsed_secs: world.resource::<LevelClock>().elapsed_secs(),
+        paddle_hits: world.resource::<SpeedProgression>().0,
+    })
+}
+
+/// 按快照重建场景系统（进入游戏状态时，在 setup_game 之后运行）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `pending`: 等待恢复的快照
+/// - `asset_server`: 资源服务器，用于生成小球
+/// - `atlas_layouts`: 纹理图集布局资源
+/// - `config`: 游戏配置
+/// - `score`: 分数资源
+/// - `lives`: 生命数资源
+/// - `remaining_bricks`: 剩余砖块数量资源
+/// - `active_power_ups`: 当前生效的道具
+/// - `run`: 本局游戏进度
+/// - `level_state`: 本关的连击、关卡计时和小球加速资源
+/// - `brick_query`: setup_game 按关卡网格生成的砖块
+/// - `ball_query`: setup_game 生成的小球
+/// - `paddle_transform`: 挡板变换组件
+/// - `next_playing_state`: 下一个游戏进行中状态
+///
+/// # 说明
+/// 删除 setup_game 生成的砖块和小球，按快照重新生成，并恢复分数、生命数、道具、连击、关卡用时、小球加速和本局游戏进度
+pub fn restore_run(
+    mut commands: Commands,
+    pending: Res<PendingResume>,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    config: Res<GameConfig>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut remaining_bricks: ResMut<RemainingBricks>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut run: ResMut<RunProgress>,
+    (mut combo, mut clock, mut progression): (ResMut<Combo>, ResMut<LevelClock>, ResMut<SpeedProgression>),
+    brick_query: Query<Entity, With<Brick>>,
+    ball_query: Query<Entity, With<Ball>>,
+    mut paddle_transform: Single<&mut Transform, With<Paddle>>,
+    mut next_playing_state: ResMut<NextState<PlayingState>>,
+) {
+    let snapshot = &pending.0;
+    commands.remove_resource::<PendingResume>();
+
+    for entity in brick_query.iter().chain(&ball_query) {
+        commands.entity(entity).despawn();
+    }
+
+    remaining_bricks.0 = 0;
+    for brick in &snapshot.bricks {
+        if spawn_brick(&mut commands, &config, brick.kind, brick.health, brick.position) {
+            remaining_bricks.0 += 1;
+        }
+    }
+
+    paddle_transform.translation = snapshot.paddle.extend(paddle_transform.translation.z);
+    for ball in &snapshot.balls {
+        let mut entity = commands.spawn(ball_bundle(
+            &asset_server,
+            &mut atlas_layouts,
+            &config,
+            ball.position.extend(1.0),
+        ));
+        match ball.velocity {
+            Some(velocity) => entity.insert(Velocity(velocity)),
+            None => entity.insert(BallAttached),
+        };
+    }
+
+    // 快照中没有小球时（例如存档文件被手动修改），在挡板上方生成一个附着的小球
+    if snapshot.balls.is_empty() {
+        let ball_y = snapshot.paddle.y + config.attached_ball_height();
+        commands.spawn((
+            ball_bundle(
+                &asset_server,
+                &mut atlas_layouts,
+                &config,
+                Vec3::new(snapshot.paddle.x, ball_y, 1.0),
+            ),
+            BallAttached,
+        ));
+    }
+
+    score.0 = snapshot.score;
+    lives.0 = snapshot.lives;
+    *run = snapshot.run.clone();
+    combo.0 = snapshot.combo;
+    clock.set_elapsed(std::time::Duration::from_secs_f32(snapshot.elapsed_secs.max(0.0)));
+    progression.0 = snapshot.paddle_hits;
+    for &(kind, remaining) in &snapshot.power_ups {
+        active_power_ups.activate(kind, remaining);
+    }
+    // 状态相同时不能重新进入 ball_attached，否则 clear_power_ups 会清除刚恢复的道具
+    let launched = snapshot.ball_launched && !snapshot.balls.is_empty();
+    NextState::set_if_neq(&mut next_playing_state, if launched {
+        PlayingState::ball_launched
+    } else {
+        PlayingState::ball_attached
+    });
+}
@@ -1,160 +1,127 @@
 // 游戏胜利界面模块
 
 use bevy::prelude::*;
-use crate::state::GameState;
-use crate::components::{VictoryRoot, BackToMenuButton};
+use crate::state::{GameState, Level, PlayingState};
+use crate::components::{ClearBonus, EndScreenButton, GameMode, RunProgress, VictoryRoot};
+use crate::high_scores::spawn_high_score_panel;
+use crate::i18n::Language;
+use crate::levels::LevelCatalog;
+use crate::widgets::{self, UiTheme};
 use crate::Score;
+use super::run::{has_next_level, start_next_level};
 
-/// 游戏胜利菜单常量配置
-mod constants {
-    use bevy::prelude::*;
+/// 游戏胜利标题颜色（金色）
+const VICTORY_TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+/// 最终分数字体大小
+const SCORE_FONT_SIZE: f32 = 40.0;
+/// 通关奖励字体大小
+const BONUS_FONT_SIZE: f32 = 26.0;
 
-    /// 游戏胜利背景颜色（半透明黑色遮罩）
-    pub const VICTORY_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
-    /// 游戏胜利标题颜色（金色）
-    pub const VICTORY_TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
-    /// 最终分数颜色
-    pub const FINAL_SCORE_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
-    /// 按钮颜色
-    pub const BUTTON_COLOR: Color = Color::srgb(0.3, 0.5, 0.8);
-    /// 按钮悬停颜色
-    pub const BUTTON_HOVER_COLOR: Color = Color::srgb(0.4, 0.6, 0.9);
-    /// 标题字体大小
-    pub const TITLE_FONT_SIZE: f32 = 60.0;
-    /// 分数字体大小
-    pub const SCORE_FONT_SIZE: f32 = 40.0;
-    /// 按钮字体大小
-    pub const BUTTON_FONT_SIZE: f32 = 30.0;
-    /// 按钮尺寸
-    pub const BUTTON_SIZE: Vec2 = Vec2::new(200.0, 60.0);
+/// 游戏胜利插件
+/// 注册游戏胜利界面的显示、交互和清理系统
+pub struct VictoryPlugin;
+
+impl Plugin for VictoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Victory), setup_victory)
+            .add_systems(Update, handle_victory_input.run_if(in_state(GameState::Victory)))
+            .add_systems(OnExit(GameState::Victory), cleanup_victory);
+    }
 }
 
 /// 设置游戏胜利界面系统
 ///
 /// # 参数
 /// - `commands`: 命令缓冲区，用于创建UI实体
-/// - `asset_server`: 资源服务器，用于加载字体资源
+/// - `theme`: 界面主题
 /// - `score`: 分数资源，用于显示最终得分
+/// - `bonus`: 本关的通关奖励
+/// - `level`: 当前关卡资源
+/// - `catalog`: 关卡目录，用于判断是否还有下一关
+/// - `run`: 本局游戏进度
+/// - `language`: 界面语言
 ///
 /// # 功能
-/// 创建游戏胜利界面，包含标题、最终分数和返回菜单按钮
+/// 创建游戏胜利界面，包含标题、最终分数（包含通关奖励）、剩余生命和用时奖励、排行榜面板和按钮：
+/// 还有下一关时显示下一关按钮，战役模式通关最后一关时显示查看成绩按钮，始终显示返回菜单按钮
 pub fn setup_victory(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
     score: Res<Score>,
+    bonus: Res<ClearBonus>,
+    level: Res<Level>,
+    catalog: Option<Res<LevelCatalog>>,
+    run: Res<RunProgress>,
+    language: Res<Language>,
 ) {
-    // 游戏胜利界面根节点（全屏遮罩）
-    let victory_root = (
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            flex_direction: FlexDirection::Column,
-            row_gap: Val::Px(30.0),
-            ..default()
-        },
-        BackgroundColor(constants::VICTORY_BACKGROUND),
-        VictoryRoot,
-    );
-
-    // 游戏胜利标题
-    let title_text = (
-        Text::new("VICTORY!"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::TITLE_FONT_SIZE,
-            ..default()
-        },
-        TextColor(constants::VICTORY_TITLE_COLOR),
-    );
-
-    // 最终分数显示
-    let score_text = (
-        Text::new(format!("最终分数: {}", score.0)),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::SCORE_FONT_SIZE,
-            ..default()
-        },
-        TextColor(constants::FINAL_SCORE_COLOR),
-    );
+    let next = if has_next_level(*level, catalog.as_deref()) {
+        Some(("下一关", EndScreenButton::NextLevel))
+    } else if run.mode == GameMode::Campaign {
+        Some(("查看成绩", EndScreenButton::CampaignResults))
+    } else {
+        None
+    };
 
-    // 返回菜单按钮
-    let back_button = (
-        Button,
-        Node {
-            width: Val::Px(constants::BUTTON_SIZE.x),
-            height: Val::Px(constants::BUTTON_SIZE.y),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            ..default()
-        },
-        BackgroundColor(constants::BUTTON_COLOR),
-        BackToMenuButton,
-    );
-
-    // 按钮文本
-    let button_text = (
-        Text::new("返回菜单"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::BUTTON_FONT_SIZE,
-            ..default()
-        },
-        TextColor(Color::WHITE),
-    );
-
-    // 生成游戏胜利界面实体树
     commands
-        .spawn(victory_root)
+        .spawn((widgets::screen(theme.overlay), VictoryRoot))
         .with_children(|parent| {
-            parent.spawn(title_text);
-            parent.spawn(score_text);
-            parent
-                .spawn(back_button)
-                .with_children(|btn_parent| {
-                    btn_parent.spawn(button_text);
-                });
+            parent.spawn(widgets::title(&theme, "VICTORY!", VICTORY_TITLE_COLOR));
+            parent.spawn(widgets::label(
+                &theme,
+                format!("{}: {}", language.tr("最终分数"), score.0),
+                SCORE_FONT_SIZE,
+                Color::WHITE,
+            ));
+            for (label, points) in [("生命奖励", bonus.lives), ("时间奖励", bonus.time)] {
+                parent.spawn(widgets::label(
+                    &theme,
+                    format!("{}: +{}", language.tr(label), points),
+                    BONUS_FONT_SIZE,
+                    Color::WHITE,
+                ));
+            }
+            spawn_high_score_panel(parent, &theme);
+            parent.spawn(widgets::row(30.0)).with_children(|bar| {
+                for (label, action) in next.into_iter().chain([("返回菜单", EndScreenButton::BackToMenu)]) {
+                    bar.spawn((
+                        widgets::button(&theme, language.tr(label), theme.overlay_button, theme.button),
+                        action,
+                    ));
+                }
+            });
         });
 }
 
-/// 处理游戏胜利界面按钮交互系统
+/// 处理游戏胜利界面按钮点击系统
 ///
 /// # 参数
-/// - `interaction_query`: 查询按钮的交互状态、背景颜色和子实体
+/// - `interaction_query`: 查询按钮的交互状态和按钮操作
+/// - `level`: 当前关卡资源
+/// - `catalog`: 关卡目录
 /// - `next_state`: 下一个游戏状态资源，用于切换状态
-/// - `text_query`: 查询文本组件，用于修改按钮文本颜色
+/// - `next_playing_state`: 下一个游戏进行中状态
 ///
 /// # 功能
-/// - 悬停时改变按钮颜色
-/// - 点击时返回主菜单状态
+/// - 下一关：保留分数和生命数进入下一关
+/// - 查看成绩：切换到战役成绩状态
+/// - 返回菜单：切换到主菜单状态
 pub fn handle_victory_input(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<BackToMenuButton>),
-    >,
+    interaction_query: Query<(&Interaction, &EndScreenButton), Changed<Interaction>>,
+    mut level: ResMut<Level>,
+    catalog: Option<Res<LevelCatalog>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut text_query: Query<&mut TextColor>,
+    mut next_playing_state: ResMut<NextState<PlayingState>>,
 ) {
-    for (interaction, mut color, children) in &mut interaction_query {
-        match *interaction {
-            Interaction::Hovered => {
-                color.0 = constants::BUTTON_HOVER_COLOR;
-                if let Ok(mut text_color) = text_query.get_mut(children[0]) {
-                    text_color.0 = Color::srgb(0.9, 0.9, 0.9);
-                }
-            }
-            Interaction::Pressed => {
-                // 返回主菜单状态
-                next_state.set(GameState::Menu);
-            }
-            Interaction::None => {
-                color.0 = constants::BUTTON_COLOR;
-                if let Ok(mut text_color) = text_query.get_mut(children[0]) {
-                    text_color.0 = Color::WHITE;
-                }
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            EndScreenButton::NextLevel => {
+                start_next_level(&mut level, catalog.as_deref(), &mut next_state, &mut next_playing_state);
             }
+            EndScreenButton::CampaignResults => next_state.set(GameState::CampaignResults),
+            _ => next_state.set(GameState::Menu),
         }
     }
 }
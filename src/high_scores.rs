@@ -0,0 +1,448 @@
+//! 最高分模块
+//! 每个关卡保存一张最高分排行榜，持久化到用户数据目录下的 RON 文件中
+//!
+//! 游戏结束或胜利时，如果本局分数能进入排行榜，结束界面会先让玩家输入名字，确认后写入文件。
+//! 文件不存在或内容损坏时使用空排行榜，不影响游戏运行
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::{RunProgress, Score};
+use crate::game::{ReplayPlayback, record_cleared_level};
+use crate::i18n::Language;
+use crate::levels::LevelCatalog;
+use crate::persist::{data_file, read_ron, write_ron_atomic};
+use crate::settings::Difficulty;
+use crate::state::{GameState, Level};
+use crate::ui_focus::UiFocusSystems;
+use crate::widgets::{self, UiTheme};
+
+/// 每个关卡排行榜保留的记录数量
+pub const HIGH_SCORE_TABLE_SIZE: usize = 5;
+/// 玩家名字的最大字符数
+pub const HIGH_SCORE_NAME_MAX_LEN: usize = 12;
+/// 玩家没有输入名字时使用的默认名字
+pub const HIGH_SCORE_DEFAULT_NAME: &str = "玩家";
+/// 排行榜文件名，保存在用户数据目录下的 `breakout` 文件夹中
+pub const HIGH_SCORE_FILE_NAME: &str = "high_scores.ron";
+/// 排行榜面板文字颜色
+pub const HIGH_SCORE_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+/// 最高分插件
+/// 启动时读取排行榜文件，并在游戏结束或胜利时处理新纪录的名字输入
+///
+/// # 说明
+/// 排行榜面板由结束界面和胜利界面通过 [`spawn_high_score_panel`] 生成，本插件负责更新面板内容
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        let file = HighScoreFile(high_score_path());
+        let high_scores = file.0.as_deref().map(HighScores::load).unwrap_or_default();
+
+        app.insert_resource(file)
+            .insert_resource(high_scores)
+            // 回放录像的分数不进入排行榜
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                start_name_entry.run_if(not(resource_exists::<ReplayPlayback>)),
+            )
+            // 在通关记录更新已通关关卡的得分之前运行，这一关的得分才不包括之前关卡的得分
+            .add_systems(
+                OnEnter(GameState::Victory),
+                start_name_entry
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .before(record_cleared_level),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_name_entry.run_if(resource_exists::<NameEntry>),
+                    update_high_score_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::GameOver).or(in_state(GameState::Victory))),
+            )
+            // 玩家没有确认名字就离开结束界面时，使用已输入的名字保存记录
+            .add_systems(OnExit(GameState::GameOver), finish_name_entry)
+            .add_systems(OnExit(GameState::Victory), finish_name_entry)
+            // 输入名字时暂停按钮焦点导航，回车和空格用于输入名字；
+            // 只用手柄的玩家按 South/East 键确认名字后恢复导航
+            .configure_sets(PreUpdate, UiFocusSystems.run_if(not(resource_exists::<NameEntry>)));
+    }
+}
+
+/// 排行榜中的一条记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// 玩家名字
+    pub name: String,
+    /// 这一关的得分（不包括从之前关卡带过来的得分）
+    pub score: usize,
+    /// 取得这个分数时的难度（旧记录中没有这一项，视为普通难度）
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+/// 最高分排行榜资源
+///
+/// 以关卡名称为键（调整关卡目录顺序不会打乱记录），每个关卡的记录按分数从高到低排列
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    /// 每个关卡的排行榜
+    pub levels: BTreeMap<String, Vec<HighScoreEntry>>,
+}
+
+impl HighScores {
+    /// 获取关卡的排行榜，没有记录时返回空列表
+    pub fn table(&self, level: &str) -> &[HighScoreEntry] {
+        self.levels.get(level).map_or(&[], Vec::as_slice)
+    }
+
+    /// 获取关卡的最高分，没有记录时返回 None
+    pub fn best(&self, level: &str) -> Option<usize> {
+        self.table(level).first().map(|entry| entry.score)
+    }
+
+    /// 分数能否进入关卡的排行榜（0 分不记录）
+    pub fn qualifies(&self, level: &str, score: usize) -> bool {
+        let table = self.table(level);
+        score > 0
+            && (table.len() < HIGH_SCORE_TABLE_SIZE
+                || table.last().is_some_and(|lowest| score > lowest.score))
+    }
+
+    /// 插入一条记录，超出排行榜长度的记录会被丢弃
+    ///
+    /// # 参数
+    /// - `level`: 关卡名称
+    /// - `name`: 玩家名字
+    /// - `score`: 分数
+    /// - `difficulty`: 取得这个分数时的难度
+    ///
+    /// # 返回值
+    /// 记录在排行榜中的名次（从0开始），没有进入排行榜时返回 None
+    ///
+    /// # 说明
+    /// 同分时先取得的记录排在前面
+    pub fn insert(&mut self, level: &str, name: String, score: usize, difficulty: Difficulty) -> Option<usize> {
+        if !self.qualifies(level, score) {
+            return None;
+        }
+        let table = self.levels.entry(level.to_string()).or_default();
+        let rank = table.partition_point(|entry| entry.score >= score);
+        table.insert(rank, HighScoreEntry { name, score, difficulty });
+        table.truncate(HIGH_SCORE_TABLE_SIZE);
+        Some(rank)
+    }
+
+    /// 从文件读取排行榜
+    ///
+    /// # 说明
+    /// 文件不存在或损坏时返回空排行榜，下次保存时会覆盖损坏的文件
+    pub fn load(path: &Path) -> Self {
+        read_ron(path, "最高分").unwrap_or_default()
+    }
+
+    /// 将排行榜保存到文件
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_ron_atomic(path, self)
+    }
+}
+
+/// 排行榜文件路径资源，无法确定用户数据目录时为 None（此时排行榜只保存在内存中）
+#[derive(Resource, Debug, Clone)]
+pub struct HighScoreFile(pub Option<PathBuf>);
+
+/// 用户数据目录下的排行榜文件路径
+pub fn high_score_path() -> Option<PathBuf> {
+    data_file(HIGH_SCORE_FILE_NAME)
+}
+
+/// 名字输入资源，本局分数能进入排行榜时存在
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct NameEntry {
+    /// 关卡名称
+    pub level: String,
+    /// 这一关的得分
+    pub score: usize,
+    /// 本局的难度
+    pub difficulty: Difficulty,
+    /// 已输入的名字
+    pub name: String,
+}
+
+/// 排行榜面板组件标记
+/// 结束界面和胜利界面中显示排行榜或名字输入框的文本
+#[derive(Component)]
+pub struct HighScorePanel;
+
+/// 生成排行榜面板
+///
+/// # 参数
+/// - `parent`: 父节点（结束界面或胜利界面的根节点）
+/// - `theme`: 界面主题
+///
+/// # 说明
+/// 面板内容由 [`update_high_score_panel`] 系统填充
+pub fn spawn_high_score_panel(parent: &mut ChildSpawnerCommands, theme: &UiTheme) {
+    parent.spawn((
+        widgets::label(theme, "", theme.text_font_size, HIGH_SCORE_TEXT_COLOR),
+        TextLayout::new_with_justify(Justify::Center),
+        HighScorePanel,
+    ));
+}
+
+/// 开始名字输入系统（进入游戏结束或胜利状态时调用）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `score`: 本局得分
+/// - `current_level`: 当前关卡资源
+/// - `catalog`: 关卡目录，用于获取关卡名称
+/// - `high_scores`: 最高分排行榜
+/// - `run`: 本局游戏进度，排行榜记录本局的难度
+///
+/// # 说明
+/// 排行榜记录的是这一关的得分：从下一关或战役模式带过来的之前关卡的得分不计入，
+/// 与直接从关卡选择进入这一关的分数可以比较。
+/// 这一关的得分能进入排行榜时插入 [`NameEntry`] 资源，结束界面随之显示名字输入框
+pub fn start_name_entry(
+    mut commands: Commands,
+    score: Res<Score>,
+    current_level: Res<Level>,
+    catalog: Option<Res<LevelCatalog>>,
+    high_scores: Res<HighScores>,
+    run: Res<RunProgress>,
+) {
+    let Some(level) = catalog.as_ref().and_then(|catalog| catalog.get(current_level.0)) else {
+        return;
+    };
+    let level_score = score.0.saturating_sub(run.banked_score());
+    if high_scores.qualifies(&level.name, level_score) {
+        commands.insert_resource(NameEntry {
+            level: level.name.clone(),
+            score: level_score,
+            difficulty: run.difficulty,
+            name: String::new(),
+        });
+    }
+}
+
+/// 处理名字输入系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `keyboard_events`: 键盘输入消息，使用其中的文本输入支持大小写和符号
+/// - `gamepads`: 已连接的手柄
+/// - `name_entry`: 名字输入资源
+/// - `high_scores`: 最高分排行榜
+/// - `file`: 排行榜文件路径
+///
+/// # 功能
+/// - 输入字符：追加到名字末尾（最多 HIGH_SCORE_NAME_MAX_LEN 个字符）
+/// - 退格键：删除最后一个字符
+/// - 回车键：确认名字，写入排行榜并保存到文件
+/// - 手柄 South/East 键（Xbox 的 A/B 键）：同回车键，没有键盘时也能离开名字输入
+///
+/// # 说明
+/// 手柄按键不通过 [`MenuInput`](crate::MenuInput) 读取，因为键盘的确认键包括空格，而空格可以出现在名字中
+pub fn handle_name_entry(
+    mut commands: Commands,
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    gamepads: Query<&Gamepad>,
+    mut name_entry: ResMut<NameEntry>,
+    mut high_scores: ResMut<HighScores>,
+    file: Res<HighScoreFile>,
+) {
+    let gamepad_confirmed = gamepads
+        .iter()
+        .any(|gamepad| gamepad.any_just_pressed([GamepadButton::South, GamepadButton::East]));
+    if gamepad_confirmed {
+        commit_name_entry(&name_entry, &mut high_scores, &file);
+        commands.remove_resource::<NameEntry>();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                commit_name_entry(&name_entry, &mut high_scores, &file);
+                commands.remove_resource::<NameEntry>();
+                return;
+            }
+            Key::Backspace => {
+                name_entry.name.pop();
+            }
+            _ => {
+                let Some(text) = &event.text else {
+                    continue;
+                };
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    if name_entry.name.chars().count() < HIGH_SCORE_NAME_MAX_LEN {
+                        name_entry.name.push(c);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 结束名字输入系统（离开游戏结束或胜利状态时调用）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `name_entry`: 名字输入资源，玩家已确认名字时不存在
+/// - `high_scores`: 最高分排行榜
+/// - `file`: 排行榜文件路径
+pub fn finish_name_entry(
+    mut commands: Commands,
+    name_entry: Option<Res<NameEntry>>,
+    mut high_scores: ResMut<HighScores>,
+    file: Res<HighScoreFile>,
+) {
+    if let Some(name_entry) = name_entry {
+        commit_name_entry(&name_entry, &mut high_scores, &file);
+        commands.remove_resource::<NameEntry>();
+    }
+}
+
+/// 把名字输入的结果写入排行榜并保存到文件
+///
+/// # 说明
+/// 名字为空时使用默认名字；保存失败只输出警告，记录仍保留在内存中
+fn commit_name_entry(name_entry: &NameEntry, high_scores: &mut HighScores, file: &HighScoreFile) {
+    let name = name_entry.name.trim();
+    let name = if name.is_empty() { HIGH_SCORE_DEFAULT_NAME } else { name };
+    high_scores.insert(&name_entry.level, name.to_string(), name_entry.score, name_entry.difficulty);
+
+    if let Some(path) = &file.0
+        && let Err(err) = high_scores.save(path)
+    {
+        warn!("无法保存最高分文件 {}: {err}", path.display());
+    }
+}
+
+/// 更新排行榜面板系统
+///
+/// # 参数
+/// - `panel_query`: 排行榜面板文本查询
+/// - `name_entry`: 名字输入资源
+/// - `high_scores`: 最高分排行榜
+/// - `current_level`: 当前关卡资源
+/// - `catalog`: 关卡目录，用于获取关卡名称
+/// - `language`: 界面语言
+///
+/// # 说明
+/// 正在输入名字时显示输入框，否则显示当前关卡的排行榜；只在内容变化时修改文本
+pub fn update_high_score_panel(
+    mut panel_query: Query<&mut Text, With<HighScorePanel>>,
+    name_entry: Option<Res<NameEntry>>,
+    high_scores: Res<HighScores>,
+    current_level: Res<Level>,
+    catalog: Option<Res<LevelCatalog>>,
+    language: Res<Language>,
+) {
+    let content = match &name_entry {
+        Some(name_entry) => {
+            format!("{}\n{}_", language.tr("新纪录！请输入名字（回车或手柄 A 键确认）"), name_entry.name)
+        }
+        None => {
+            let Some(level) = catalog.as_ref().and_then(|catalog| catalog.get(current_level.0)) else {
+                return;
+            };
+            high_score_table_text(high_scores.table(&level.name), *language)
+        }
+    };
+
+    for mut text in &mut panel_query {
+        if text.0 != content {
+            text.0 = content.clone();
+        }
+    }
+}
+
+/// 排行榜的显示文本
+pub fn high_score_table_text(table: &[HighScoreEntry], language: Language) -> String {
+    if table.is_empty() {
+        return language.tr("暂无最高分记录").to_string();
+    }
+    let mut text = language.tr("最高分排行榜").to_string();
+    for (rank, entry) in table.iter().enumerate() {
+        text.push_str(&format!(
+            "\n{}. {}  {}  ({})",
+            rank + 1,
+            entry.name,
+            entry.score,
+            language.tr(entry.difficulty.label())
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn entry(name: &str, score: usize) -> HighScoreEntry {
+        HighScoreEntry {
+            name: name.to_string(),
+            score,
+            difficulty: Difficulty::Normal,
+        }
+    }
+
+    #[test]
+    fn insert_keeps_table_sorted_and_bounded() {
+        let mut high_scores = HighScores::default();
+        for score in [30, 10, 50, 20, 40, 60] {
+            high_scores.insert("关卡", format!("P{score}"), score, Difficulty::Normal);
+        }
+        let scores: Vec<usize> = high_scores.table("关卡").iter().map(|entry| entry.score).collect();
+        assert_eq!(scores, vec![60, 50, 40, 30, 20]);
+        assert_eq!(high_scores.best("关卡"), Some(60));
+        assert_eq!(high_scores.best("其他关卡"), None);
+    }
+
+    #[test]
+    fn qualifies_only_when_beating_the_lowest_entry_of_a_full_table() {
+        let mut high_scores = HighScores::default();
+        assert!(!high_scores.qualifies("关卡", 0));
+        for score in 1..=HIGH_SCORE_TABLE_SIZE {
+            high_scores.insert("关卡", "P".to_string(), score * 10, Difficulty::Normal);
+        }
+        assert!(!high_scores.qualifies("关卡", 10));
+        assert!(high_scores.qualifies("关卡", 11));
+        // 同分时先取得的记录排在前面
+        assert_eq!(high_scores.insert("关卡", "新".to_string(), 30, Difficulty::Normal), Some(3));
+        assert_eq!(high_scores.table("关卡")[2], entry("P", 30));
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_loads_an_empty_table() {
+        let dir = std::env::temp_dir().join(format!("breakout-high-scores-{}", std::process::id()));
+        let path = dir.join(HIGH_SCORE_FILE_NAME);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(HighScores::load(&path), HighScores::default());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "这不是 RON (").unwrap();
+        assert_eq!(HighScores::load(&path), HighScores::default());
+
+        let mut high_scores = HighScores::default();
+        high_scores.insert("关卡", "玩家".to_string(), 42, Difficulty::Hard);
+        high_scores.save(&path).unwrap();
+        assert_eq!(HighScores::load(&path), high_scores);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+//! 界面语言模块
+//! 界面文字以中文写在代码中，选择英文时通过 [`Language::tr`] 查表翻译
+//!
+//! 切换语言后，已经显示的界面在下一次打开时使用新语言
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 界面语言资源
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Language {
+    /// 中文
+    #[default]
+    Chinese,
+    /// 英文
+    English,
+}
+
+impl Language {
+    /// 所有语言，设置界面按这个顺序切换
+    pub const ALL: [Language; 2] = [Language::Chinese, Language::English];
+
+    /// 语言名称（始终使用该语言本身显示）
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Chinese => "中文",
+            Language::English => "English",
+        }
+    }
+
+    /// 翻译界面文字
+    ///
+    /// # 参数
+    /// - `text`: 中文原文
+    ///
+    /// # 返回值
+    /// 中文时原样返回；英文时返回译文，没有译文时返回原文
+    pub fn tr(self, text: &'static str) -> &'static str {
+        match self {
+            Language::Chinese => text,
+            Language::English => english(text).unwrap_or(text),
+        }
+    }
+}
+
+/// 英文译文表
+fn english(text: &str) -> Option<&'static str> {
+    Some(match text {
+        // 主菜单
+        "打砖块游戏" => "Breakout",
+        "继续游戏" => "Continue",
+        "开始游戏" => "Start Game",
+        "战役模式" => "Campaign",
+        "设置" => "Settings",
+        // 设置界面
+        "主音量" => "Master Volume",
+        "音效音量" => "SFX Volume",
+        "音乐音量" => "Music Volume",
+        "显示模式" => "Display Mode",
+        "窗口" => "Windowed",
+        "全屏" => "Fullscreen",
+        "垂直同步" => "VSync",
+        "开" => "On",
+        "关" => "Off",
+        "语言" => "Language",
+        "难度" => "Difficulty",
+        "简单" => "Easy",
+        "普通" => "Normal",
+        "困难" => "Hard",
+        "输入设备" => "Input Device",
+        "键盘" => "Keyboard",
+        "手柄" => "Gamepad",
+        "鼠标" => "Mouse",
+        "鼠标灵敏度" => "Mouse Sensitivity",
+        // 按键设置界面
+        "按键设置" => "Controls",
+        "恢复默认" => "Restore Defaults",
+        "返回" => "Back",
+        "请按下新按键..." => "Press a new key...",
+        "未绑定" => "Unbound",
+        "向左移动" => "Move Left",
+        "向右移动" => "Move Right",
+        "发射" => "Launch",
+        "暂停" => "Pause",
+        "确认" => "Confirm",
+        "回车" => "Enter",
+        "空格" => "Space",
+        "退格" => "Backspace",
+        // 关卡选择界面
+        "选择关卡" => "Select Level",
+        "上一页" => "Previous",
+        "下一页" => "Next",
+        "关卡加载中..." => "Loading levels...",
+        "未解锁" => "Locked",
+        "最高分" => "Best",
+        // 游戏中
+        "按 {} 键发射小球" => "Press {} to launch",
+        "按手柄 A 键发射小球" => "Press A on the gamepad to launch",
+        "点击鼠标发射小球" => "Click to launch",
+        "连击" => "Combo",
+        "加宽" => "Wide",
+        "多球" => "Multiball",
+        "减速" => "Slow",
+        "粘板" => "Sticky",
+        "激光" => "Laser",
+        "生命" => "Extra Life",
+        "重新开始" => "Restart",
+        "返回菜单" => "Main Menu",
+        // 结束界面和排行榜
+        "最终分数" => "Final Score",
+        "生命奖励" => "Life Bonus",
+        "时间奖励" => "Time Bonus",
+        "重试" => "Retry",
+        "下一关" => "Next Level",
+        "查看成绩" => "Results",
+        "战役完成" => "Campaign Complete",
+        "总分" => "Total Score",
+        "新纪录！请输入名字（回车或手柄 A 键确认）" => "New record! Enter your name (Enter or gamepad A to confirm)",
+        "暂无最高分记录" => "No high scores yet",
+        "最高分排行榜" => "High Scores",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untranslated_text_falls_back_to_chinese() {
+        assert_eq!(Language::Chinese.tr("开始游戏"), "开始游戏");
+        assert_eq!(Language::English.tr("开始游戏"), "Start Game");
+        assert_eq!(Language::English.tr("没有译文"), "没有译文");
+    }
+}
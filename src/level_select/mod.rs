@@ -1,176 +1,433 @@
 //! 关卡选择模块
 //! 提供关卡选择界面，允许玩家选择不同的关卡进行游戏
+//!
+//! 关卡按钮根据关卡目录动态生成，支持翻页、键盘导航，并显示每个关卡的最高分和解锁状态
 
 use bevy::prelude::*;
+use crate::assets_tracking::ResourceHandles;
+use crate::components::LevelProgress;
+use crate::game::ReplayPlayback;
+use crate::high_scores::HighScores;
+use crate::i18n::Language;
+use crate::levels::LevelCatalog;
 use crate::state::{GameState, Level};
-use crate::menu::constants;
+use crate::ui_focus::{MenuInput, UiFocusSystems};
+use crate::widgets::{self, ButtonColors, UiTheme};
+
+/// 每页显示的关卡数量
+pub const LEVELS_PER_PAGE: usize = 4;
+/// 关卡按钮之间的间距
+const LEVEL_BUTTON_GAP: f32 = 20.0;
+
+/// 关卡选择插件
+/// 注册关卡选择界面的系统，并在通关时记录关卡进度
+///
+/// # 说明
+/// 关卡按钮上的最高分来自 [`HighScores`]，需要在 [`HighScorePlugin`](crate::high_scores::HighScorePlugin) 之后添加
+pub struct LevelSelectPlugin;
+
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelProgress>()  // 初始化关卡进度资源（解锁状态）
+            .init_resource::<LevelSelectCursor>()  // 初始化关卡选择光标
+            .add_systems(OnEnter(GameState::LevelSelect), setup_level_select)
+            .add_systems(
+                Update,
+                (
+                    handle_level_select_input,
+                    handle_level_select_keyboard,
+                    update_level_list,
+                    highlight_selected_level,
+                )
+                    .run_if(in_state(GameState::LevelSelect))
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::LevelSelect), cleanup_level_select)
+            // 关卡选择界面使用自己的光标导航，不使用通用的按钮焦点
+            .configure_sets(PreUpdate, UiFocusSystems.run_if(not(in_state(GameState::LevelSelect))))
+            // 回放录像通关不解锁关卡
+            .add_systems(
+                OnEnter(GameState::Victory),
+                record_level_progress.run_if(not(resource_exists::<ReplayPlayback>)),
+            );
+    }
+}
 
 /// 关卡选择UI根节点标记组件
 #[derive(Component)]
 pub struct LevelSelectRoot;
 
-/// 关卡选择按钮类型
+/// 关卡选择按钮，保存对应关卡在关卡目录中的索引
 #[derive(Component)]
-pub enum LevelButton {
-    /// 第一关按钮
-    Level1,
-    /// 第二关按钮
-    Level2,
+pub struct LevelButton(pub usize);
+
+/// 翻页按钮类型
+#[derive(Component)]
+pub enum LevelPageButton {
+    /// 上一页
+    Previous,
+    /// 下一页
+    Next,
+}
+
+/// 关卡按钮列表容器
+/// 记录当前显示的页码，页码变化时重新生成按钮
+#[derive(Component, Default)]
+pub struct LevelListUi {
+    /// 当前显示的页码，尚未生成按钮时为 None
+    pub page: Option<usize>,
+}
+
+/// 页码文本组件标记
+#[derive(Component)]
+pub struct LevelPageText;
+
+/// 关卡选择光标资源
+/// 鼠标悬停和键盘导航共用同一个光标
+#[derive(Resource, Default, Debug)]
+pub struct LevelSelectCursor {
+    /// 当前选中的关卡索引
+    pub selected: usize,
+    /// 当前页码
+    pub page: usize,
+}
+
+impl LevelSelectCursor {
+    /// 选中指定关卡，并翻到该关卡所在的页
+    pub fn select(&mut self, level: usize) {
+        self.selected = level;
+        self.page = level / LEVELS_PER_PAGE;
+    }
+}
+
+/// 计算关卡目录的总页数（至少为1页）
+fn page_count(catalog: Option<&LevelCatalog>) -> usize {
+    catalog
+        .map_or(0, |catalog| catalog.len().div_ceil(LEVELS_PER_PAGE))
+        .max(1)
 }
 
 /// 初始化关卡选择界面系统
 ///
 /// # 参数
 /// - `commands`: 命令缓冲区，用于创建UI实体
-/// - `asset_server`: 资源服务器，用于加载字体资源
+/// - `theme`: 界面主题
+/// - `cursor`: 关卡选择光标，进入界面时选中当前关卡
+/// - `current_level`: 当前关卡资源
+/// - `language`: 界面语言
 ///
 /// # 说明
-/// 创建关卡选择界面，包含标题和两个关卡选择按钮
-pub fn setup_level_select(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // 关卡选择容器（垂直布局）
-    let container = (
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            flex_direction: FlexDirection::Column,
-            row_gap: Val::Px(30.0),
-            ..default()
-        },
-        BackgroundColor(constants::MENU_BACKGROUND_COLOR),
-        LevelSelectRoot,
-    );
-
-    // 标题文本
-    let title_text = (
-        Text::new("选择关卡"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::MENU_TITLE_FONT_SIZE,
-            ..default()
-        },
-        TextColor(constants::MENU_TITLE_COLOR),
-        Node {
-            margin: UiRect::bottom(Val::Px(30.0)),
-            ..default()
-        },
-    );
-
-    // 第一关按钮文本
-    let level1_text = (
-        Text::new("第一关 - 简单"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::MENU_BUTTON_FONT_SIZE,
-            ..default()
-        },
-        TextColor(Color::WHITE),
-    );
+/// 创建关卡选择界面的骨架：标题、关卡按钮列表容器和翻页栏
+/// 关卡按钮由 [`update_level_list`] 根据关卡目录生成
+pub fn setup_level_select(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    mut cursor: ResMut<LevelSelectCursor>,
+    current_level: Res<Level>,
+    language: Res<Language>,
+) {
+    cursor.select(current_level.0);
 
-    // 第一关按钮
-    let level1_button = (
-        Button,
+    // 关卡按钮列表容器，固定高度避免翻页时翻页栏上下跳动
+    let level_list = (
         Node {
-            width: Val::Px(250.0),
-            height: Val::Px(60.0),
+            height: Val::Px(
+                LEVELS_PER_PAGE as f32 * theme.level_button.size.y + (LEVELS_PER_PAGE - 1) as f32 * LEVEL_BUTTON_GAP,
+            ),
+            flex_direction: FlexDirection::Column,
             align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(LEVEL_BUTTON_GAP),
             ..default()
         },
-        BackgroundColor(constants::MENU_BUTTON_COLOR),
-        LevelButton::Level1,
+        LevelListUi::default(),
     );
+    let page_button = |label: &'static str, page_button: LevelPageButton| {
+        (widgets::button(&theme, language.tr(label), theme.menu_button, theme.page_button), page_button)
+    };
 
-    // 第二关按钮文本
-    let level2_text = (
-        Text::new("第二关 - 困难"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::MENU_BUTTON_FONT_SIZE,
-            ..default()
-        },
-        TextColor(Color::WHITE),
-    );
+    // 生成关卡选择界面实体树：标题、关卡按钮列表和翻页栏
+    commands
+        .spawn((widgets::screen(theme.background), LevelSelectRoot))
+        .with_children(|parent| {
+            parent.spawn(widgets::title(&theme, language.tr("选择关卡"), theme.title_color));
+            parent.spawn(level_list);
+            parent.spawn(widgets::row(30.0)).with_children(|bar| {
+                bar.spawn(page_button("上一页", LevelPageButton::Previous));
+                bar.spawn((
+                    widgets::label(&theme, "", theme.small_font_size, theme.title_color),
+                    LevelPageText,
+                ));
+                bar.spawn(page_button("下一页", LevelPageButton::Next));
+            });
+        });
+}
 
-    // 第二关按钮
-    let level2_button = (
-        Button,
-        Node {
-            width: Val::Px(250.0),
-            height: Val::Px(60.0),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            ..default()
-        },
-        BackgroundColor(constants::MENU_BUTTON_COLOR),
-        LevelButton::Level2,
-    );
+/// 生成关卡按钮列表系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `theme`: 界面主题
+/// - `cursor`: 关卡选择光标，决定显示哪一页
+/// - `catalog`: 关卡目录资源（加载完成前不存在）
+/// - `progress`: 关卡进度资源，用于显示解锁状态
+/// - `high_scores`: 最高分排行榜，用于显示每关的最高分
+/// - `list_query`: 关卡按钮列表容器
+/// - `page_text`: 页码文本
+/// - `language`: 界面语言
+///
+/// # 说明
+/// 当页码变化或关卡目录加载完成时，重新生成当前页的关卡按钮
+pub fn update_level_list(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    cursor: Res<LevelSelectCursor>,
+    catalog: Option<Res<LevelCatalog>>,
+    progress: Res<LevelProgress>,
+    high_scores: Res<HighScores>,
+    list_query: Single<(Entity, &mut LevelListUi)>,
+    mut page_text: Single<&mut Text, With<LevelPageText>>,
+    language: Res<Language>,
+) {
+    let (list_entity, mut list) = list_query.into_inner();
+    let catalog_added = catalog.as_ref().is_some_and(|catalog| catalog.is_added());
+    if list.page == Some(cursor.page) && !catalog_added {
+        return;
+    }
+
+    let Some(catalog) = catalog else {
+        page_text.0 = language.tr("关卡加载中...").to_string();
+        return;
+    };
+    list.page = Some(cursor.page);
+    page_text.0 = format!("{} / {}", cursor.page + 1, page_count(Some(&catalog)));
 
-    // 生成关卡选择界面实体树
+    // 删除上一页的按钮，生成当前页的按钮
+    let first = cursor.page * LEVELS_PER_PAGE;
+    let last = (first + LEVELS_PER_PAGE).min(catalog.len());
     commands
-        .spawn(container)
+        .entity(list_entity)
+        .despawn_children()
         .with_children(|parent| {
-            // 添加标题
-            parent.spawn(title_text);
-            // 添加第一关按钮
-            parent
-                .spawn(level1_button)
-                .with_children(|btn_parent| {
-                    btn_parent.spawn(level1_text);
-                });
-            // 添加第二关按钮
-            parent
-                .spawn(level2_button)
-                .with_children(|btn_parent| {
-                    btn_parent.spawn(level2_text);
-                });
+            for index in first..last {
+                let level = &catalog.levels[index];
+                // 按钮右侧显示最高分或未解锁提示
+                let info = if !progress.is_unlocked(index) {
+                    language.tr("未解锁").to_string()
+                } else if let Some(best) = high_scores.best(&level.name) {
+                    format!("{}: {best}", language.tr("最高分"))
+                } else {
+                    format!("{}: -", language.tr("最高分"))
+                };
+
+                // 关卡按钮的颜色由 highlight_selected_level 根据光标和解锁状态设置
+                parent
+                    .spawn((
+                        widgets::button_frame(
+                            theme.menu_button,
+                            Node {
+                                justify_content: JustifyContent::SpaceBetween,
+                                padding: UiRect::horizontal(Val::Px(20.0)),
+                                ..widgets::button_node(theme.level_button.size)
+                            },
+                        ),
+                        LevelButton(index),
+                    ))
+                    .with_children(|btn_parent| {
+                        btn_parent.spawn(widgets::label(
+                            &theme,
+                            level.name.clone(),
+                            theme.level_button.font_size,
+                            theme.button_text,
+                        ));
+                        btn_parent.spawn(widgets::label(&theme, info, theme.small_font_size, theme.button_text));
+                    });
+            }
         });
 }
 
+/// 高亮选中关卡系统
+///
+/// # 参数
+/// - `cursor`: 关卡选择光标
+/// - `progress`: 关卡进度资源，未解锁的关卡显示为灰色
+/// - `theme`: 界面主题
+/// - `button_query`: 关卡按钮查询
+///
+/// # 说明
+/// 选中的关卡始终使用悬停颜色，未解锁的关卡始终显示为灰色；
+/// 只修改按钮的 [`ButtonColors`]，实际颜色由统一的按钮颜色系统设置
+pub fn highlight_selected_level(
+    cursor: Res<LevelSelectCursor>,
+    progress: Res<LevelProgress>,
+    theme: Res<UiTheme>,
+    mut button_query: Query<(&LevelButton, &mut ButtonColors)>,
+) {
+    for (level_button, mut colors) in &mut button_query {
+        colors.set_if_neq(if !progress.is_unlocked(level_button.0) {
+            theme.locked_button
+        } else if level_button.0 == cursor.selected {
+            ButtonColors::solid(theme.menu_button.hovered)
+        } else {
+            theme.menu_button
+        });
+    }
+}
+
+/// 尝试开始指定关卡
+///
+/// # 返回值
+/// 关卡已解锁且资源已加载完成时切换到游戏状态并返回 true
+fn try_start_level(
+    index: usize,
+    progress: &LevelProgress,
+    resource_handles: &ResourceHandles,
+    level_resource: &mut Level,
+    next_state: &mut NextState<GameState>,
+) -> bool {
+    // 关卡数据尚未加载完成或关卡未解锁，暂不进入游戏
+    if !resource_handles.is_all_done() || !progress.is_unlocked(index) {
+        return false;
+    }
+    *level_resource = Level(index);
+    next_state.set(GameState::Playing);
+    true
+}
+
 /// 处理关卡选择按钮交互系统
 ///
 /// # 参数
-/// - `interaction_query`: 查询按钮的交互状态、背景颜色和关卡按钮类型
+/// - `level_button_query`: 查询关卡按钮的交互状态
+/// - `page_button_query`: 查询翻页按钮的交互状态
+/// - `cursor`: 关卡选择光标
+/// - `catalog`: 关卡目录资源，用于计算总页数
+/// - `progress`: 关卡进度资源，未解锁的关卡无法进入
 /// - `next_state`: 下一个游戏状态资源
 /// - `level_resource`: 当前关卡资源（可变）
+/// - `resource_handles`: 资源加载追踪器，关卡目录等资源加载完成前不允许开始游戏
 ///
 /// # 功能
-/// - 悬停时改变按钮颜色
-/// - 点击时设置对应关卡并切换到游戏状态
+/// - 悬停关卡按钮时选中该关卡
+/// - 点击关卡按钮时设置对应关卡并切换到游戏状态
+/// - 点击翻页按钮时切换页码
 pub fn handle_level_select_input(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &LevelButton),
-        (Changed<Interaction>, With<Button>),
-    >,
+    level_button_query: Query<(&Interaction, &LevelButton), Changed<Interaction>>,
+    page_button_query: Query<(&Interaction, &LevelPageButton), Changed<Interaction>>,
+    mut cursor: ResMut<LevelSelectCursor>,
+    catalog: Option<Res<LevelCatalog>>,
+    progress: Res<LevelProgress>,
     mut next_state: ResMut<NextState<GameState>>,
     mut level_resource: ResMut<Level>,
+    resource_handles: Res<ResourceHandles>,
 ) {
-    for (interaction, mut color, level_button) in &mut interaction_query {
+    for (interaction, level_button) in &level_button_query {
         match *interaction {
             Interaction::Hovered => {
-                color.0 = constants::MENU_BUTTON_HOVER_COLOR;
-            }
-            Interaction::Pressed => {
-                // 根据按钮类型设置关卡
-                match level_button {
-                    LevelButton::Level1 => {
-                        *level_resource = Level::Level1;
-                    }
-                    LevelButton::Level2 => {
-                        *level_resource = Level::Level2;
-                    }
+                // 只在选中项变化时写入，避免触发无意义的变更检测
+                if cursor.selected != level_button.0 {
+                    cursor.selected = level_button.0;
                 }
-                // 切换到游戏状态
-                next_state.set(GameState::Playing);
             }
-            Interaction::None => {
-                color.0 = constants::MENU_BUTTON_COLOR;
+            Interaction::Pressed => {
+                try_start_level(
+                    level_button.0,
+                    &progress,
+                    &resource_handles,
+                    &mut level_resource,
+                    &mut next_state,
+                );
             }
+            Interaction::None => {}
+        }
+    }
+
+    let pages = page_count(catalog.as_deref());
+    let page = cursor.page;
+    for (interaction, page_button) in &page_button_query {
+        if *interaction == Interaction::Pressed {
+            let target_page = match page_button {
+                LevelPageButton::Previous => page.saturating_sub(1),
+                LevelPageButton::Next => (page + 1).min(pages - 1),
+            };
+            cursor.select(target_page * LEVELS_PER_PAGE);
         }
     }
 }
 
+/// 处理关卡选择键盘和手柄导航系统
+///
+/// # 参数
+/// - `menu_input`: 界面操作资源（键盘和手柄）
+/// - `cursor`: 关卡选择光标
+/// - `catalog`: 关卡目录资源（加载完成前不存在）
+/// - `progress`: 关卡进度资源，未解锁的关卡无法进入
+/// - `next_state`: 下一个游戏状态资源
+/// - `level_resource`: 当前关卡资源（可变）
+/// - `resource_handles`: 资源加载追踪器
+///
+/// # 功能
+/// - 上/下（方向键、十字键或摇杆）：选择上一个/下一个关卡，必要时自动翻页
+/// - 左/右（方向键、向左/向右移动键、十字键或摇杆）：翻到上一页/下一页
+/// - 确认（确认键或手柄 A 键）：进入选中的关卡
+/// - 返回（返回键或手柄 B 键）：返回主菜单
+pub fn handle_level_select_keyboard(
+    menu_input: Res<MenuInput>,
+    mut cursor: ResMut<LevelSelectCursor>,
+    catalog: Option<Res<LevelCatalog>>,
+    progress: Res<LevelProgress>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut level_resource: ResMut<Level>,
+    resource_handles: Res<ResourceHandles>,
+) {
+    if menu_input.back {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let Some(catalog) = catalog else {
+        return;
+    };
+    if catalog.is_empty() {
+        return;
+    }
+    let last_level = catalog.len() - 1;
+    let last_page = page_count(Some(&catalog)) - 1;
+    let (selected, page) = (cursor.selected, cursor.page);
+
+    if menu_input.up {
+        cursor.select(selected.saturating_sub(1));
+    }
+    if menu_input.down {
+        cursor.select((selected + 1).min(last_level));
+    }
+    if menu_input.left {
+        cursor.select(page.saturating_sub(1) * LEVELS_PER_PAGE);
+    }
+    if menu_input.right {
+        cursor.select((page + 1).min(last_page) * LEVELS_PER_PAGE);
+    }
+    if menu_input.confirm {
+        try_start_level(
+            cursor.selected,
+            &progress,
+            &resource_handles,
+            &mut level_resource,
+            &mut next_state,
+        );
+    }
+}
+
+/// 记录关卡进度系统（进入胜利状态时调用）
+///
+/// # 参数
+/// - `current_level`: 当前关卡资源
+/// - `progress`: 关卡进度资源
+///
+/// # 说明
+/// 记录通关的关卡，解锁下一关；最高分由 [`HighScorePlugin`](crate::high_scores::HighScorePlugin) 记录
+pub fn record_level_progress(current_level: Res<Level>, mut progress: ResMut<LevelProgress>) {
+    progress.record_cleared(current_level.0);
+}
+
 /// 清理关卡选择界面系统
 ///
 /// # 参数
@@ -0,0 +1,151 @@
+//! 关卡数据模块
+//! 关卡布局由 `assets/levels` 下的 RON 文件描述，设计人员无需修改代码即可新增关卡
+//!
+//! - `catalog.levels.ron`：关卡目录，按顺序列出所有关卡文件
+//! - `*.level.ron`：单个关卡，用字符网格描述每个格子的砖块类型（字符含义见 [`BrickKind::from_cell`]）
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::assets_tracking::LoadResource;
+use crate::components::BrickKind;
+
+/// 关卡目录文件路径（相对于 assets 目录）
+pub const LEVEL_CATALOG_PATH: &str = "levels/catalog.levels.ron";
+
+/// 关卡数据插件
+/// 注册关卡目录加载器，并通过 assets_tracking 异步加载关卡目录（需要在 `assets_tracking::plugin` 之后添加）
+pub fn plugin(app: &mut App) {
+    app.init_asset::<LevelCatalog>();
+    app.register_asset_loader(LevelCatalogLoader);
+    app.load_resource_from_path::<LevelCatalog>(LEVEL_CATALOG_PATH);
+}
+
+/// 单个关卡的数据
+#[derive(Debug, Clone, Deserialize, Reflect)]
+pub struct LevelData {
+    /// 关卡名称，显示在关卡选择界面
+    pub name: String,
+    /// 砖块网格，第一行字符串为最上方一行砖块，最后一行紧贴砖块区域底部
+    pub grid: Vec<String>,
+}
+
+impl LevelData {
+    /// 网格的行数
+    pub fn rows(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// 网格的列数（取最长的一行）
+    pub fn columns(&self) -> usize {
+        self.grid.iter().map(|row| row.chars().count()).max().unwrap_or(0)
+    }
+
+    /// 遍历所有砖块格子
+    ///
+    /// # 返回值
+    /// `(row, column, kind)` 迭代器，其中 `row` 从底部开始计数（0 为最下方一行）
+    pub fn bricks(&self) -> impl Iterator<Item = (usize, usize, BrickKind)> + '_ {
+        let n_rows = self.rows();
+        self.grid.iter().enumerate().flat_map(move |(line, cells)| {
+            cells.chars().enumerate().filter_map(move |(column, cell)| {
+                // 加载时已经校验过字符，这里忽略无法识别的格子
+                let kind = BrickKind::from_cell(cell).ok().flatten()?;
+                Some((n_rows - 1 - line, column, kind))
+            })
+        })
+    }
+
+    /// 校验关卡数据
+    fn validate(&self) -> Result<(), String> {
+        if self.grid.is_empty() {
+            return Err(format!("关卡 \"{}\" 的砖块网格为空", self.name));
+        }
+        for (line, cells) in self.grid.iter().enumerate() {
+            for cell in cells.chars() {
+                if let Err(cell) = BrickKind::from_cell(cell) {
+                    return Err(format!(
+                        "关卡 \"{}\" 第 {} 行包含无法识别的字符 '{}'",
+                        self.name,
+                        line + 1,
+                        cell
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 关卡目录资源
+/// 按顺序保存所有已加载的关卡，由 [`Level`](crate::state::Level) 索引
+///
+/// 通过 assets_tracking 模块从 [`LEVEL_CATALOG_PATH`] 异步加载，加载完成后才会插入为资源
+#[derive(Resource, Asset, Clone, Debug, Reflect)]
+pub struct LevelCatalog {
+    /// 所有关卡，顺序与目录文件一致
+    pub levels: Vec<LevelData>,
+}
+
+impl LevelCatalog {
+    /// 根据索引获取关卡数据
+    pub fn get(&self, index: usize) -> Option<&LevelData> {
+        self.levels.get(index)
+    }
+
+    /// 关卡数量
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// 目录中是否没有任何关卡
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+}
+
+/// 关卡目录文件的内容
+#[derive(Deserialize)]
+struct LevelCatalogFile {
+    /// 关卡文件路径列表（相对于 assets 目录）
+    levels: Vec<String>,
+}
+
+/// 关卡目录加载器
+/// 读取目录文件，并依次读取其中列出的每个关卡文件
+#[derive(Default, TypePath)]
+struct LevelCatalogLoader;
+
+impl AssetLoader for LevelCatalogLoader {
+    type Asset = LevelCatalog;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let catalog: LevelCatalogFile = ron::de::from_bytes(&bytes)?;
+
+        let mut levels = Vec::with_capacity(catalog.levels.len());
+        for path in catalog.levels {
+            // 通过 LoadContext 读取，关卡文件会被记录为目录的依赖
+            let bytes = load_context.read_asset_bytes(path.clone()).await?;
+            let level: LevelData = ron::de::from_bytes(&bytes)
+                .map_err(|err| format!("无法解析关卡文件 {path}: {err}"))?;
+            level.validate()?;
+            levels.push(level);
+        }
+
+        Ok(LevelCatalog { levels })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["levels.ron"]
+    }
+}
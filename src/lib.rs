@@ -0,0 +1,147 @@
+//! 打砖块游戏库
+//! 游戏的所有模块都在这里声明，每个功能都提供一个插件：
+//! - [`MenuPlugin`]：开始菜单和全局相机
+//! - [`LevelSelectPlugin`]：关卡选择和关卡进度
+//! - [`GameplayPlugin`]：游戏进行中的所有系统（可以无窗口运行）
+//! - [`WidgetPlugin`]：界面主题和各界面共用的按钮组件
+//! - [`UiFocusPlugin`]：所有界面按钮的键盘和手柄焦点导航
+//! - [`PausePlugin`]：暂停菜单
+//! - [`HighScorePlugin`]：每关的最高分排行榜（保存在用户数据目录中）
+//! - [`SavePlugin`]：保存进行中的一局游戏，主菜单可以继续游戏
+//! - [`SettingsPlugin`]：读取和保存玩家的偏好设置（音量、显示、语言、难度、输入设备、按键绑定）
+//! - [`SettingsMenuPlugin`]：设置界面
+//! - [`ControlsPlugin`]：按键设置界面
+//! - [`ReplayPlugin`]：保存每局游戏的输入录像，并回放命令行指定的录像
+//! - [`GameOverPlugin`]、[`VictoryPlugin`]：游戏结束和胜利界面（重试、下一关）
+//! - [`CampaignResultsPlugin`]：战役模式通关后的成绩界面
+//! - [`AudioPlugin`]：音效和背景音乐
+//! - `config::plugin`：加载可热重载的游戏配置 [`GameConfig`]
+//!
+//! [`BreakoutPlugin`] 插件组把它们组合成完整的游戏，`main.rs` 只需在 `DefaultPlugins` 之后添加它。
+//! 集成测试（`tests/`）则可以只加载 [`GameplayPlugin`]
+
+use bevy::app::PluginGroupBuilder;
+
+// 引入菜单模块
+mod menu;
+pub use menu::{setup_menu, handle_menu_input, cleanup_menu, MenuAction, MenuPlugin};
+
+// 引入关卡选择模块
+mod level_select;
+pub use level_select::{
+    setup_level_select, update_level_list, highlight_selected_level, handle_level_select_input,
+    handle_level_select_keyboard, record_level_progress, cleanup_level_select, LevelSelectCursor,
+    LevelSelectPlugin,
+};
+
+// 引入配置模块
+mod config;
+pub use config::*;
+
+// 引入资产加载模块
+pub mod assets_tracking;
+
+// 引入音频模块
+mod audio;
+pub use audio::*;
+
+// 引入持久化模块
+mod persist;
+pub use persist::*;
+
+// 引入最高分模块
+mod high_scores;
+pub use high_scores::*;
+
+// 引入存档模块
+mod save;
+pub use save::*;
+
+// 引入输入动作模块
+mod actions;
+pub use actions::*;
+
+// 引入界面语言模块
+mod i18n;
+pub use i18n::*;
+
+// 引入界面组件模块
+pub mod widgets;
+pub use widgets::{ButtonColors, ButtonLabel, ButtonSize, UiTheme, WidgetPlugin};
+
+// 引入界面焦点模块
+mod ui_focus;
+pub use ui_focus::*;
+
+// 引入设置界面模块
+mod settings_menu;
+pub use settings_menu::{SettingsMenuPlugin, SettingsOption};
+
+// 引入按键设置模块
+mod controls;
+pub use controls::{ControlsPlugin, RebindTarget};
+
+// 引入设置模块
+mod settings;
+pub use settings::*;
+
+// 引入录像文件模块（录制和回放逻辑在 game::replay 中）
+mod replay_file;
+pub use replay_file::*;
+
+// 引入关卡数据模块
+pub mod levels;
+pub use levels::*;
+
+// 引入组件模块
+mod components;
+pub use components::*;
+
+// 引入系统模块
+mod system;
+pub use system::*;
+
+// 引入状态模块
+mod state;
+pub use state::*;
+
+// 引入游戏模块
+mod game;
+pub use game::*;
+
+/// 打砖块游戏插件组
+/// 按依赖顺序组合所有功能插件，需要在 `DefaultPlugins` 之后添加
+///
+/// # 说明
+/// 嵌入其他程序时可以通过 `BreakoutPlugin.build().disable::<AudioPlugin>()` 等方式去掉不需要的功能
+pub struct BreakoutPlugin;
+
+impl PluginGroup for BreakoutPlugin {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            // 资产追踪插件，音效和关卡目录都通过它异步加载
+            .add(assets_tracking::plugin)
+            .add(config::plugin)
+            // 关卡数据插件，注册关卡目录加载器并异步加载关卡目录
+            .add(levels::plugin)
+            .add(AudioPlugin)
+            .add(GameplayPlugin)
+            // 设置插件在游戏玩法插件之后添加，用设置文件中的值覆盖默认设置
+            .add(SettingsPlugin)
+            // 最高分插件需要在关卡选择插件之前添加，关卡选择界面会读取排行榜
+            .add(HighScorePlugin)
+            // 存档插件需要在菜单插件之前添加，主菜单根据存档决定是否显示继续游戏按钮
+            .add(SavePlugin)
+            .add(ReplayPlugin)
+            .add(WidgetPlugin)
+            .add(UiFocusPlugin)
+            .add(MenuPlugin)
+            .add(LevelSelectPlugin)
+            .add(SettingsMenuPlugin)
+            .add(ControlsPlugin)
+            .add(PausePlugin)
+            .add(GameOverPlugin)
+            .add(VictoryPlugin)
+            .add(CampaignResultsPlugin)
+    }
+}
@@ -2,39 +2,49 @@
 //! Bevy 0.18 适配
 
 pub use bevy::prelude::*;
+use crate::config::BACKGROUND_COLOR;
+use crate::state::GameState;
 
-// 导出菜单相关常量
-pub mod constants {
-    use bevy::prelude::*;
-
-    /// 菜单标题字体大小
-    pub const MENU_TITLE_FONT_SIZE: f32 = 60.0;
-    /// 菜单按钮字体大小
-    pub const MENU_BUTTON_FONT_SIZE: f32 = 40.0;
-    /// 菜单按钮尺寸
-    pub const MENU_BUTTON_SIZE: Vec2 = Vec2::new(300.0, 80.0);
-    /// 菜单按钮间距
-    pub const MENU_BUTTON_GAP: f32 = 40.0;
-    /// 菜单按钮默认颜色
-    pub const MENU_BUTTON_COLOR: Color = Color::srgb(0.3, 0.3, 0.7);
-    /// 菜单按钮悬停颜色
-    pub const MENU_BUTTON_HOVER_COLOR: Color = Color::srgb(0.4, 0.4, 0.8);
-    /// 菜单标题颜色
-    pub const MENU_TITLE_COLOR: Color = Color::srgb(0.2, 0.2, 0.6);
-    /// 菜单背景颜色
-    pub const MENU_BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+/// 主菜单按钮的操作类型
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    /// 继续上次的游戏，只在有存档时显示
+    Continue,
+    /// 开始游戏（进入关卡选择）
+    Start,
+    /// 开始战役模式：从第一关开始依次挑战所有关卡
+    Campaign,
+    /// 切换下一局的难度
+    Difficulty,
+    /// 进入设置界面
+    Settings,
 }
 
-/// 菜单按钮组件标记
-/// 用于标识菜单中的可交互按钮
-#[derive(Component)]
-pub struct MenuButton;
-
-/// 菜单根节点组件标记
-/// 用于标识菜单UI的根实体，便于清理
-#[derive(Component)]
-pub struct MenuRoot;
-
 // 导出菜单系统
 pub mod systems;
-pub use systems::*;
\ No newline at end of file
+pub use systems::*;
+/// 菜单插件
+/// 创建全局相机，并注册开始菜单的显示、交互和清理系统
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClearColor(BACKGROUND_COLOR))  // 设置背景颜色
+            // 在启动时创建相机，只运行一次
+            .add_systems(Startup, setup_camera)
+            .add_systems(OnEnter(GameState::Menu), setup_menu)
+            .add_systems(Update, handle_menu_input.run_if(in_state(GameState::Menu)))
+            .add_systems(OnExit(GameState::Menu), cleanup_menu);
+    }
+}
+
+/// 初始化相机系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于创建相机实体
+///
+/// # 说明
+/// 在应用启动时创建一次2D相机，供整个游戏使用
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
@@ -3,8 +3,16 @@
 //! Bevy 0.18 版本适配
 
 use bevy::prelude::*;
-use super::{constants, MenuButton};
-use crate::state::GameState;  // 引用主程序定义的游戏状态
+use super::MenuAction;
+use crate::assets_tracking::ResourceHandles;
+use crate::components::{Lives, RunProgress, Score};
+use crate::game::start_campaign;
+use crate::i18n::Language;
+use crate::levels::LevelCatalog;
+use crate::save::{SavedRun, resume_run};
+use crate::settings::Difficulty;
+use crate::state::{GameState, Level, PlayingState};  // 引用主程序定义的游戏状态
+use crate::widgets::{self, UiTheme};
 
 /// 菜单UI根节点标记组件
 /// 用于后续清理菜单时识别菜单相关实体
@@ -15,134 +23,128 @@ pub struct MenuRoot;
 /// 
 /// # 参数
 /// - `commands`: 命令缓冲区，用于创建UI实体
-/// - `asset_server`: 资源服务器，用于加载字体资源
+/// - `theme`: 界面主题
+/// - `saved_run`: 当前存档（没有添加存档插件时不存在）
+/// - `language`: 界面语言
+/// - `difficulty`: 难度设置，显示在难度按钮上
 /// 
 /// # 说明
-/// 创建菜单界面，包含标题和开始游戏按钮
+/// 创建菜单界面，包含标题、开始游戏、战役模式、难度和设置按钮，有存档时还会显示继续游戏按钮
 /// 注意：相机在应用启动时已经创建，此处不再重复创建
-pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-
-    // 菜单容器（垂直布局）- Bevy 0.18 使用 Node 组件替代 NodeBundle
-    //Node 是 Bevy UI 中最基础的布局容器组件，用于控制元素的尺寸、排列方式等核心布局属性：
-    let menu_container = (
-        Node {
-            width: Val::Percent(100.0),  // 宽度占父容器的 100%（全屏宽度）
-            height: Val::Percent(100.0),   // 高度占父容器的 100%（全屏高度）
-            align_items: AlignItems::Center,  // 垂直方向（交叉轴）上居中对齐子元素
-            justify_content: JustifyContent::Center,  // 水平方向（主轴）上居中对齐子元素
-            flex_direction: FlexDirection::Column,  // 子元素按垂直列方向排列（从上到下）
-            ..default()
-        },
-        //BackgroundColor 是 Bevy 提供的 UI 组件，用于设置容器的背景颜色。
-        BackgroundColor(constants::MENU_BACKGROUND_COLOR), 
-        MenuRoot,  // 标记为菜单根节点，便于清理
-    );
-
-    // 标题文本 - Bevy 0.18 使用 Text 和 TextFont 组件
-    let title_text = (
-        Text::new("打砖块游戏"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"), // 使用项目中已有的字体
-            font_size: constants::MENU_TITLE_FONT_SIZE,
-            ..default()
-        },
-        TextColor(constants::MENU_TITLE_COLOR),
-        Node {
-            //给文本的底部添加 50 像素的外边距，作用是留出间距，避免元素挤在一起。
-            //UiRect 还支持 top/left/right/all 等方法
-            margin: UiRect::bottom(Val::Px(50.0)),
-            ..default()
-        },
-    );
-
-    // 开始游戏按钮 - Bevy 0.18 使用 Button 组件
-    let start_button = (
-        //Bevy 内置的，赋予实体 “可点击按钮” 的交互能力。
-        Button,
-        Node {
-            width: Val::Px(constants::MENU_BUTTON_SIZE.x),
-            height: Val::Px(constants::MENU_BUTTON_SIZE.y),
-            align_items: AlignItems::Center,  // 按钮内文本垂直居中
-            justify_content: JustifyContent::Center,  // 按钮内文本水平居中
-            ..default()
-        },
-        BackgroundColor(constants::MENU_BUTTON_COLOR),
-        MenuButton,  // 标记为菜单按钮
-    );
-
-    // 按钮文本
-    let button_text = (
-        Text::new("开始游戏"),
-        TextFont {
-            font: asset_server.load("fonts/songti.ttf"),
-            font_size: constants::MENU_BUTTON_FONT_SIZE,
-            ..default()
-        },
-        TextColor(Color::WHITE),
-    );
+pub fn setup_menu(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    saved_run: Option<Res<SavedRun>>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+) {
+    let has_saved_run = saved_run.is_some_and(|saved_run| saved_run.0.is_some());
+    let difficulty_label = format!("{}: {}", language.tr("难度"), language.tr(difficulty.label()));
+    let buttons = [
+        (MenuAction::Continue, language.tr("继续游戏").to_string()),
+        (MenuAction::Start, language.tr("开始游戏").to_string()),
+        (MenuAction::Campaign, language.tr("战役模式").to_string()),
+        (MenuAction::Difficulty, difficulty_label),
+        (MenuAction::Settings, language.tr("设置").to_string()),
+    ];
 
-    // 生成菜单实体树 - Bevy 0.18 使用 children! 宏
+    // 生成菜单实体树：标题和按钮垂直排列
     commands
-        .spawn(menu_container)
+        .spawn((widgets::screen(theme.background), MenuRoot))
         .with_children(|parent| {
-            // 添加标题
-            parent.spawn(title_text);
-            // 添加开始按钮
-            parent
-                .spawn(start_button)
-                .with_children(|btn_parent| {
-                    btn_parent.spawn(button_text);
-                });
+            parent.spawn(widgets::title(&theme, language.tr("打砖块游戏"), theme.title_color));
+            for (action, label) in buttons {
+                // 继续游戏按钮只在有存档时生成
+                if action == MenuAction::Continue && !has_saved_run {
+                    continue;
+                }
+                parent.spawn((
+                    widgets::button(&theme, label, theme.menu_button, theme.large_button),
+                    action,
+                ));
+            }
         });
 }
 
-/// 处理菜单按钮交互（悬停/点击）系统
+/// 处理菜单按钮点击系统
 /// 
 /// # 参数
-/// - `interaction_query`: 查询按钮的交互状态、背景颜色和子实体
+/// - `commands`: 命令缓冲区，继续游戏时插入等待恢复的存档
+/// - `interaction_query`: 查询按钮的交互状态和按钮操作
 /// - `next_state`: 下一个游戏状态资源，用于切换状态
-/// - `text_query`: 查询文本组件，用于修改按钮文本颜色
+/// - `saved_run`: 当前存档
+/// - `catalog`: 关卡目录，用于确认存档中的关卡仍然存在
+/// - `resource_handles`: 资源加载追踪器，资源加载完成前不允许继续游戏
+/// - `level`: 当前关卡资源
+/// - `run`: 本局游戏进度，开始战役模式时重置
+/// - `score`: 分数资源
+/// - `lives`: 生命数资源
+/// - `difficulty`: 难度设置
+/// - `next_playing_state`: 下一个游戏进行中状态
 /// 
 /// # 功能
-/// - 悬停时改变按钮颜色
-/// - 点击时切换到游戏状态
+/// - 点击开始游戏时切换到关卡选择状态
+/// - 点击继续游戏时按存档恢复上次的游戏
+/// - 点击战役模式时从第一关开始战役（资源加载完成前不响应）
+/// - 点击难度时切换到下一个难度并保存到设置文件，重新进入菜单状态以更新按钮文字和本局的难度、生命数
+/// - 点击设置时切换到设置状态
+///
+/// 按钮的悬停颜色由 [`update_button_colors`](crate::widgets::update_button_colors) 统一处理
 pub fn handle_menu_input(
-    //✅ &Interaction：读取按钮的交互状态（悬停 / 点击 / 无交互）；
-    //✅ &mut BackgroundColor：可变引用按钮的背景色，用于动态修改；
-    //✅ &Children：读取按钮的子实体列表（按钮文本是按钮的子实体）。
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<MenuButton>),
-    >,
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &MenuAction), Changed<Interaction>>,
     //Bevy 的状态管理资源，用于修改游戏全局状态（比如从 Menu 切到 Playing）。
     mut next_state: ResMut<NextState<GameState>>,
-    //用于查询并修改文本的颜色组件
-    mut text_query: Query<&mut TextColor>,
+    saved_run: Option<Res<SavedRun>>,
+    catalog: Option<Res<LevelCatalog>>,
+    resource_handles: Res<ResourceHandles>,
+    mut level: ResMut<Level>,
+    mut run: ResMut<RunProgress>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut difficulty: ResMut<Difficulty>,
+    mut next_playing_state: ResMut<NextState<PlayingState>>,
 ) {
-    for (interaction, mut color, children) in &mut interaction_query {
-        match *interaction {
-            // 鼠标悬停
-            Interaction::Hovered => {
-                color.0 = constants::MENU_BUTTON_HOVER_COLOR;
-                // 修改按钮文本颜色 
-                //text_query.get_mut(children[0])：通过文本实体 ID 获取文本的 TextColor 组件并修改，
-                //将文本颜色改为浅灰色（srgb(0.9, 0.9, 0.9)），提升悬停时的视觉反馈。
-                if let Ok(mut text_color) = text_query.get_mut(children[0]) {
-                    text_color.0 = Color::srgb(0.9, 0.9, 0.9);
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            MenuAction::Continue => {
+                // 关卡目录等资源加载完成、且存档中的关卡仍然存在时才能继续
+                if !resource_handles.is_all_done() {
+                    continue;
+                }
+                let snapshot = saved_run
+                    .as_deref()
+                    .zip(catalog.as_deref())
+                    .and_then(|(saved_run, catalog)| saved_run.resumable(catalog));
+                if let Some(snapshot) = snapshot {
+                    resume_run(&mut commands, snapshot, &mut level, &mut next_state);
                 }
             }
-            // 鼠标点击
-            Interaction::Pressed => {
-                // 切换到关卡选择状态
-                next_state.set(GameState::LevelSelect);
-            }
-            // 无交互
-            Interaction::None => {
-                color.0 = constants::MENU_BUTTON_COLOR;
-                if let Ok(mut text_color) = text_query.get_mut(children[0]) {
-                    text_color.0 = Color::WHITE;
+            // 切换到关卡选择状态
+            MenuAction::Start => next_state.set(GameState::LevelSelect),
+            MenuAction::Campaign => {
+                if !resource_handles.is_all_done() {
+                    continue;
                 }
+                start_campaign(
+                    &mut score,
+                    &mut lives,
+                    *difficulty,
+                    &mut level,
+                    &mut run,
+                    &mut next_state,
+                    &mut next_playing_state,
+                );
+            }
+            MenuAction::Difficulty => {
+                *difficulty = difficulty.next();
+                // set 对相同状态也会触发 OnExit/OnEnter，reset_game_state 随之使用新的难度
+                next_state.set(GameState::Menu);
             }
+            MenuAction::Settings => next_state.set(GameState::Settings),
         }
     }
 }
@@ -0,0 +1,98 @@
+//! 持久化模块
+//! 需要保存在本地的数据共用的 RON 文件读写
+//!
+//! - 所有文件都保存在用户目录下的 `breakout` 文件夹中
+//! - 写入时先写临时文件再重命名，读取失败时由调用方决定使用什么默认值
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// 游戏在用户目录下使用的文件夹名
+pub const DATA_DIR_NAME: &str = "breakout";
+
+/// 用户数据目录下的游戏文件路径
+///
+/// # 参数
+/// - `name`: 相对于 `breakout` 文件夹的文件路径
+///
+/// # 返回值
+/// 无法确定用户数据目录时返回 None
+pub fn data_file(name: impl AsRef<Path>) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR_NAME).join(name))
+}
+
+/// 用户配置目录下的游戏文件路径
+///
+/// # 参数
+/// - `name`: 相对于 `breakout` 文件夹的文件路径
+///
+/// # 返回值
+/// 无法确定用户配置目录时返回 None
+pub fn config_file(name: impl AsRef<Path>) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(DATA_DIR_NAME).join(name))
+}
+
+/// 从 RON 文件读取数据
+///
+/// # 参数
+/// - `path`: 文件路径
+/// - `kind`: 文件的用途（例如“最高分”），用于警告信息
+///
+/// # 返回值
+/// 文件不存在时返回 None；文件无法读取或已损坏时输出警告并返回 None
+pub fn read_ron<T: DeserializeOwned>(path: &Path, kind: &str) -> Option<T> {
+    match fs::read_to_string(path) {
+        Ok(content) => ron::from_str(&content)
+            .inspect_err(|err| warn!("{kind}文件 {} 已损坏，忽略该文件: {err}", path.display()))
+            .ok(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => {
+            warn!("无法读取{kind}文件 {}，忽略该文件: {err}", path.display());
+            None
+        }
+    }
+}
+
+/// 将数据写入 RON 文件，目录不存在时自动创建
+///
+/// # 说明
+/// 先写入临时文件再重命名，避免写入过程中退出导致文件损坏
+pub fn write_ron_atomic<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    let temp_path = path.with_extension("ron.tmp");
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn written_file_reads_back_and_leaves_no_temporary_file() {
+        let dir = std::env::temp_dir().join(format!("breakout-persist-{}", std::process::id()));
+        let path = dir.join("nested").join("data.ron");
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(read_ron::<BTreeMap<String, u32>>(&path, "测试"), None);
+
+        let value = BTreeMap::from([("关卡".to_string(), 3)]);
+        write_ron_atomic(&path, &value).unwrap();
+        assert_eq!(read_ron(&path, "测试"), Some(value));
+        assert!(!path.with_extension("ron.tmp").exists());
+
+        fs::write(&path, "这不是 RON (").unwrap();
+        assert_eq!(read_ron::<BTreeMap<String, u32>>(&path, "测试"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! 录像文件模块
+//! 把每局游戏的输入录像保存到用户数据目录，并支持通过命令行参数回放录像
+//!
+//! - 离开游戏状态（失败、胜利、回到菜单）和关闭程序时保存最近一局的录像
+//! - 启动时传入 `--replay <录像文件>`，关卡目录加载完成后自动回放
+//! - 录像的关卡不在关卡目录中时放弃回放；游戏配置与录制时不同时仍然回放，但结果可能不一致
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use crate::assets_tracking::ResourceHandles;
+use crate::config::GameConfig;
+use crate::game::{Replay, ReplayPlayback, ReplayRecorder, config_hash};
+use crate::levels::LevelCatalog;
+use crate::persist::{data_file, write_ron_atomic};
+use crate::state::{GameState, Level};
+
+/// 最近一局录像的文件名，保存在用户数据目录下的 `breakout/replays` 文件夹中
+pub const LAST_REPLAY_FILE_NAME: &str = "last_run.replay.ron";
+
+/// 录像插件
+/// 保存每局游戏的录像，并处理命令行请求的回放
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReplayFile(replay_path()))
+            .add_systems(OnExit(GameState::Playing), save_replay)
+            // 关闭窗口退出程序时不会经过 OnExit(Playing)，未结束的录像在 Last 中保存
+            .add_systems(
+                Last,
+                save_replay.run_if(in_state(GameState::Playing).and(on_message::<AppExit>)),
+            )
+            .add_systems(
+                Update,
+                start_requested_replay
+                    .run_if(in_state(GameState::Menu).and(resource_exists::<ReplayRequest>)),
+            );
+    }
+}
+
+/// 录像文件路径资源，无法确定用户数据目录时为 None（此时不保存录像）
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayFile(pub Option<PathBuf>);
+
+/// 回放请求资源，由 `main.rs` 根据命令行参数插入，回放开始后移除
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayRequest(pub PathBuf);
+
+/// 用户数据目录下最近一局录像的文件路径
+pub fn replay_path() -> Option<PathBuf> {
+    data_file(Path::new("replays").join(LAST_REPLAY_FILE_NAME))
+}
+
+/// 从文件读取录像
+pub fn load_replay(path: &Path) -> io::Result<Replay> {
+    let content = fs::read_to_string(path)?;
+    ron::from_str(&content).map_err(io::Error::other)
+}
+
+/// 保存录像系统（离开游戏状态或退出程序时调用）
+///
+/// # 参数
+/// - `recorder`: 录像记录器，取出当前录像后停止录制
+/// - `file`: 录像文件路径
+///
+/// # 说明
+/// 没有录制任何步长的录像（例如刚进入游戏就退出）不保存，避免覆盖上一局的录像
+pub fn save_replay(mut recorder: ResMut<ReplayRecorder>, file: Res<ReplayFile>) {
+    let Some(replay) = recorder.0.take() else {
+        return;
+    };
+    if replay.inputs.is_empty() {
+        return;
+    }
+    if let Some(path) = &file.0
+        && let Err(err) = write_ron_atomic(path, &replay)
+    {
+        warn!("无法保存录像文件 {}: {err}", path.display());
+    }
+}
+
+/// 开始回放系统（在菜单中，存在回放请求时运行）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `request`: 回放请求
+/// - `resource_handles`: 资源加载追踪器，关卡目录加载完成后才开始回放
+/// - `catalog`: 关卡目录
+/// - `config`: 当前游戏配置，用于检查与录制时是否一致
+/// - `level`: 当前关卡资源
+/// - `next_state`: 下一个游戏状态
+pub fn start_requested_replay(
+    mut commands: Commands,
+    request: Res<ReplayRequest>,
+    resource_handles: Res<ResourceHandles>,
+    catalog: Option<Res<LevelCatalog>>,
+    config: Res<GameConfig>,
+    mut level: ResMut<Level>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !resource_handles.is_all_done() {
+        return;
+    }
+    commands.remove_resource::<ReplayRequest>();
+
+    let path = &request.0;
+    let replay = match load_replay(path) {
+        Ok(replay) => replay,
+        Err(err) => {
+            warn!("无法读取录像文件 {}: {err}", path.display());
+            return;
+        }
+    };
+    let level_matches = catalog
+        .and_then(|catalog| catalog.get(replay.level).map(|level| level.name == replay.level_name))
+        .unwrap_or(false);
+    if !level_matches {
+        warn!("录像 {} 的关卡「{}」不在关卡目录中，放弃回放", path.display(), replay.level_name);
+        return;
+    }
+    if replay.config_hash != config_hash(&config) {
+        warn!("录像 {} 录制时的游戏配置与当前配置不同，回放结果可能不一致", path.display());
+    }
+
+    level.0 = replay.level;
+    commands.insert_resource(ReplayPlayback::new(replay));
+    next_state.set(GameState::Playing);
+}
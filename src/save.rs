@@ -0,0 +1,158 @@
+//! 存档模块
+//! 把进行中的一局游戏保存到用户数据目录，主菜单的“继续游戏”按钮从存档重建场景
+//!
+//! - 暂停（包括窗口失去焦点自动暂停）和关闭程序时保存存档
+//! - 写入过存档或从存档继续的那一局结束（失败或胜利）时删除存档，新开的一局结束时保留之前的存档
+//! - 存档文件不存在或损坏时视为没有存档
+//! - 回放录像时既不写入也不删除存档
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use crate::game::{PendingResume, ReplayPlayback, RunSnapshot, capture_run};
+use crate::levels::LevelCatalog;
+use crate::persist::{data_file, read_ron, write_ron_atomic};
+use crate::state::{GameState, Level, PauseState};
+
+/// 存档文件名，保存在用户数据目录下的 `breakout` 文件夹中
+pub const SAVE_FILE_NAME: &str = "saved_run.ron";
+
+/// 存档插件
+/// 启动时读取存档，并在暂停、退出程序和一局游戏结束时更新存档
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        let file = SaveFile(save_path());
+        let saved_run = SavedRun(file.0.as_deref().and_then(|path| read_ron(path, "存档")));
+
+        app.insert_resource(file)
+            .insert_resource(saved_run)
+            // 回放录像时不写入也不删除存档
+            .add_systems(
+                OnEnter(PauseState::Paused),
+                save_run.run_if(not(resource_exists::<ReplayPlayback>)),
+            )
+            // 关闭窗口时程序在本帧结束后退出，在 Last 中保存
+            .add_systems(
+                Last,
+                save_run.run_if(
+                    in_state(GameState::Playing)
+                        .and(on_message::<AppExit>)
+                        .and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                delete_saved_run.run_if(
+                    resource_exists::<OwnsSavedRun>.and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::Victory),
+                delete_saved_run.run_if(
+                    resource_exists::<OwnsSavedRun>.and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            // 回到菜单后开始的是新的一局（继续游戏时重新标记）
+            .add_systems(OnEnter(GameState::Menu), release_saved_run);
+    }
+}
+
+/// 存档文件路径资源，无法确定用户数据目录时为 None（此时存档只保存在内存中）
+#[derive(Resource, Debug, Clone)]
+pub struct SaveFile(pub Option<PathBuf>);
+
+/// 当前存档资源，没有存档时为 None
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SavedRun(pub Option<RunSnapshot>);
+
+/// 存档归属标记资源：当前这局游戏从存档继续，或者已经写入过存档
+///
+/// # 说明
+/// 只有带着这个标记的一局结束时才删除存档，避免新开的一局结束时删掉之前保存的另一局
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct OwnsSavedRun;
+
+impl SavedRun {
+    /// 取出可以继续的存档：存档存在且对应的关卡仍在关卡目录中
+    pub fn resumable(&self, catalog: &LevelCatalog) -> Option<&RunSnapshot> {
+        self.0.as_ref().filter(|snapshot| snapshot.matches(catalog))
+    }
+}
+
+/// 用户数据目录下的存档文件路径
+pub fn save_path() -> Option<PathBuf> {
+    data_file(SAVE_FILE_NAME)
+}
+
+/// 保存存档系统（进入暂停状态或退出程序时调用）
+///
+/// # 参数
+/// - `world`: 游戏世界，通过 [`capture_run`] 记录当前这局游戏
+///
+/// # 说明
+/// 写入文件失败只输出警告，存档仍保留在内存中，本次运行期间仍可继续游戏
+pub fn save_run(world: &mut World) {
+    let Some(snapshot) = capture_run(world) else {
+        return;
+    };
+    if let Some(path) = &world.resource::<SaveFile>().0
+        && let Err(err) = write_ron_atomic(path, &snapshot)
+    {
+        warn!("无法保存存档文件 {}: {err}", path.display());
+    }
+    world.resource_mut::<SavedRun>().0 = Some(snapshot);
+    world.insert_resource(OwnsSavedRun);
+}
+
+/// 删除存档系统（写入过存档或从存档继续的一局结束时调用）
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于移除 [`OwnsSavedRun`]
+/// - `saved_run`: 当前存档
+/// - `file`: 存档文件路径
+pub fn delete_saved_run(mut commands: Commands, mut saved_run: ResMut<SavedRun>, file: Res<SaveFile>) {
+    commands.remove_resource::<OwnsSavedRun>();
+    if saved_run.0.take().is_none() {
+        return;
+    }
+    if let Some(path) = &file.0
+        && let Err(err) = fs::remove_file(path)
+        && err.kind() != io::ErrorKind::NotFound
+    {
+        warn!("无法删除存档文件 {}: {err}", path.display());
+    }
+}
+
+/// 释放存档归属系统（回到菜单时调用）
+///
+/// # 说明
+/// 存档仍然保留，之后通过“继续游戏”恢复时会重新标记归属
+pub fn release_saved_run(mut commands: Commands) {
+    commands.remove_resource::<OwnsSavedRun>();
+}
+
+/// 继续存档中的一局游戏
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于插入 [`PendingResume`] 和 [`OwnsSavedRun`]
+/// - `snapshot`: 要继续的存档
+/// - `level`: 当前关卡资源
+/// - `next_state`: 下一个游戏状态
+///
+/// # 说明
+/// 设置关卡后切换到游戏状态，进入游戏时由 [`restore_run`](crate::game::restore_run) 按存档重建场景
+pub fn resume_run(
+    commands: &mut Commands,
+    snapshot: &RunSnapshot,
+    level: &mut Level,
+    next_state: &mut NextState<GameState>,
+) {
+    level.0 = snapshot.level;
+    commands.insert_resource(PendingResume(snapshot.clone()));
+    commands.insert_resource(OwnsSavedRun);
+    next_state.set(GameState::Playing);
+}
@@ -0,0 +1,333 @@
+//! 设置模块
+//! 把玩家的偏好设置（音量、显示、语言、难度、输入设备、按键绑定）保存到用户配置目录，启动时读取并应用
+//!
+//! - 设置文件中省略的字段使用默认值，文件不存在或损坏时全部使用默认值
+//! - 设置资源被修改后自动写回设置文件
+//! - 显示设置修改后立即应用到主窗口
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+use crate::actions::KeyBindings;
+use crate::game::InputSettings;
+use crate::i18n::Language;
+use crate::persist::{config_file, read_ron, write_ron_atomic};
+
+/// 设置文件名，保存在用户配置目录下的 `breakout` 文件夹中
+pub const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+/// 设置插件
+/// 启动时读取设置文件并插入各项设置资源，设置修改后保存
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let file = SettingsFile(settings_path());
+        let settings = file
+            .0
+            .as_deref()
+            .and_then(|path| read_ron::<Settings>(path, "设置"))
+            .map(Settings::sanitized)
+            .unwrap_or_default();
+
+        app.insert_resource(file)
+            .insert_resource(settings.audio)
+            .insert_resource(settings.video)
+            .insert_resource(settings.language)
+            .insert_resource(settings.difficulty)
+            .insert_resource(settings.input)
+            .insert_resource(settings.bindings)
+            .add_systems(
+                Update,
+                (
+                    // 启动时也会运行一次，应用设置文件中的显示设置
+                    apply_video_settings.run_if(resource_changed::<VideoSettings>),
+                    save_settings,
+                ),
+            );
+    }
+}
+
+/// 音量设置资源，音量范围为 0.0 到 1.0
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// 主音量，同时作用于音效和音乐
+    pub master: f32,
+    /// 音效音量
+    pub sfx: f32,
+    /// 音乐音量
+    pub music: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            sfx: 1.0,
+            music: 0.6,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// 音效的实际音量（主音量 × 音效音量）
+    pub fn sfx_volume(&self) -> f32 {
+        self.master * self.sfx
+    }
+
+    /// 音乐的实际音量（主音量 × 音乐音量）
+    pub fn music_volume(&self) -> f32 {
+        self.master * self.music
+    }
+}
+
+/// 显示设置资源
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoSettings {
+    /// 是否全屏（无边框全屏）
+    pub fullscreen: bool,
+    /// 是否开启垂直同步
+    pub vsync: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            vsync: true,
+        }
+    }
+}
+
+/// 难度资源
+///
+/// # 说明
+/// 每局游戏开始前选择，决定这局的生命数、小球速度及加速上限、挡板宽度和小球反弹的最大角度；
+/// 游戏中使用记录在 [`RunProgress`](crate::components::RunProgress) 中的难度，回放录像时不会改变设置
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// 简单
+    Easy,
+    /// 普通
+    #[default]
+    Normal,
+    /// 困难
+    Hard,
+}
+
+impl Difficulty {
+    /// 所有难度，设置界面按这个顺序切换
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// 难度名称（中文原文，显示时通过 [`Language::tr`] 翻译）
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "简单",
+            Difficulty::Normal => "普通",
+            Difficulty::Hard => "困难",
+        }
+    }
+
+    /// 下一个难度，困难之后回到简单
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|difficulty| *difficulty == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// 每局游戏开始时的生命数
+    pub fn starting_lives(self) -> usize {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+        }
+    }
+
+    /// 小球初始速度相对配置中小球速度的倍数
+    pub fn ball_speed_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.85,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.2,
+        }
+    }
+
+    /// 小球加速的上限（相对初始速度的倍数），见 [`SpeedProgression`](crate::components::SpeedProgression)
+    pub fn max_speed_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.3,
+            Difficulty::Normal => 1.6,
+            Difficulty::Hard => 2.0,
+        }
+    }
+
+    /// 挡板宽度相对配置中挡板宽度的倍数
+    pub fn paddle_width_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.25,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.8,
+        }
+    }
+
+    /// 小球从挡板反弹时偏离竖直方向的最大角度（弧度），角度越大越难控制
+    pub fn max_bounce_angle(self) -> f32 {
+        use std::f32::consts::PI;
+        match self {
+            Difficulty::Easy => PI / 4.0,        // 45度
+            Difficulty::Normal => PI / 3.0,      // 60度
+            Difficulty::Hard => PI * 5.0 / 12.0, // 75度
+        }
+    }
+}
+
+/// 设置文件的内容
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 音量设置
+    pub audio: AudioSettings,
+    /// 显示设置
+    pub video: VideoSettings,
+    /// 界面语言
+    pub language: Language,
+    /// 难度
+    pub difficulty: Difficulty,
+    /// 输入设置
+    pub input: InputSettings,
+    /// 按键绑定
+    pub bindings: KeyBindings,
+}
+
+impl Settings {
+    /// 修正超出范围的设置：音量限制在 0.0 到 1.0 之间，无效的鼠标灵敏度恢复为默认值
+    fn sanitized(mut self) -> Self {
+        for volume in [&mut self.audio.master, &mut self.audio.sfx, &mut self.audio.music] {
+            *volume = if volume.is_nan() { 1.0 } else { volume.clamp(0.0, 1.0) };
+        }
+        let sensitivity = self.input.mouse_sensitivity;
+        if sensitivity.is_nan() || sensitivity <= 0.0 {
+            warn!("鼠标灵敏度 {sensitivity} 无效，使用默认值");
+            self.input.mouse_sensitivity = InputSettings::default().mouse_sensitivity;
+        }
+        self
+    }
+}
+
+/// 设置文件路径资源，无法确定用户配置目录时为 None（此时设置不会保存）
+#[derive(Resource, Debug, Clone)]
+pub struct SettingsFile(pub Option<PathBuf>);
+
+/// 用户配置目录下的设置文件路径
+pub fn settings_path() -> Option<PathBuf> {
+    config_file(SETTINGS_FILE_NAME)
+}
+
+/// 应用显示设置系统（显示设置修改后调用）
+///
+/// # 参数
+/// - `video`: 显示设置
+/// - `window`: 主窗口（无窗口运行时不存在）
+pub fn apply_video_settings(video: Res<VideoSettings>, window: Option<Single<&mut Window, With<PrimaryWindow>>>) {
+    let Some(mut window) = window else {
+        return;
+    };
+    window.mode = if video.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    window.present_mode = if video.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync };
+}
+
+/// 保存设置系统（每帧检查，任意设置资源被修改后写入文件）
+///
+/// # 参数
+/// - `file`: 设置文件路径
+/// - `audio`: 音量设置
+/// - `video`: 显示设置
+/// - `language`: 界面语言
+/// - `difficulty`: 难度
+/// - `input`: 输入设置
+/// - `bindings`: 按键绑定
+///
+/// # 说明
+/// 启动时插入资源也算作修改，跳过这一次
+pub fn save_settings(
+    file: Res<SettingsFile>,
+    audio: Res<AudioSettings>,
+    video: Res<VideoSettings>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+    input: Res<InputSettings>,
+    bindings: Res<KeyBindings>,
+) {
+    let changed = audio.is_changed()
+        || video.is_changed()
+        || language.is_changed()
+        || difficulty.is_changed()
+        || input.is_changed()
+        || bindings.is_changed();
+    if !changed || input.is_added() {
+        return;
+    }
+    let Some(path) = &file.0 else {
+        return;
+    };
+    let settings = Settings {
+        audio: audio.clone(),
+        video: video.clone(),
+        language: *language,
+        difficulty: *difficulty,
+        input: input.clone(),
+        bindings: bindings.clone(),
+    };
+    if let Err(err) = write_ron_atomic(path, &settings) {
+        warn!("无法保存设置文件 {}: {err}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::InputSource;
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let settings: Settings = ron::from_str("(input: (source: Mouse))").unwrap();
+        assert_eq!(settings.input.source, InputSource::Mouse);
+        assert_eq!(settings.input.mouse_sensitivity, InputSettings::default().mouse_sensitivity);
+
+        let settings: Settings = ron::from_str("()").unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn out_of_range_values_are_fixed() {
+        let settings: Settings = ron::from_str("(input: (mouse_sensitivity: -2.0))").unwrap();
+        let settings = settings.sanitized();
+        assert_eq!(settings.input.mouse_sensitivity, InputSettings::default().mouse_sensitivity);
+
+        let settings: Settings = ron::from_str("(audio: (master: 3.0, sfx: -1.0))").unwrap();
+        let settings = settings.sanitized();
+        assert_eq!((settings.audio.master, settings.audio.sfx), (1.0, 0.0));
+    }
+
+    #[test]
+    fn harder_difficulties_are_faster_and_less_forgiving() {
+        for pair in Difficulty::ALL.windows(2) {
+            let (easier, harder) = (pair[0], pair[1]);
+            assert_eq!(easier.next(), harder);
+            assert!(harder.starting_lives() < easier.starting_lives());
+            assert!(harder.ball_speed_scale() > easier.ball_speed_scale());
+            assert!(harder.max_speed_scale() > easier.max_speed_scale());
+            assert!(harder.paddle_width_scale() < easier.paddle_width_scale());
+            assert!(harder.max_bounce_angle() > easier.max_bounce_angle());
+        }
+        assert_eq!(Difficulty::Hard.next(), Difficulty::Easy);
+    }
+}
@@ -0,0 +1,340 @@
+//! 设置界面模块
+//! 从主菜单进入的设置界面，每个选项一行：左侧为选项名称，右侧为显示当前值的按钮，点击按钮切换到下一个值
+//!
+//! 修改后的设置由 [`SettingsPlugin`](crate::SettingsPlugin) 自动保存到设置文件，显示设置立即应用到窗口
+
+use bevy::prelude::*;
+use crate::game::{InputSettings, InputSource};
+use crate::i18n::Language;
+use crate::settings::{AudioSettings, Difficulty, VideoSettings};
+use crate::state::GameState;
+use crate::ui_focus::MenuInput;
+use crate::widgets::{self, ButtonLabel, UiTheme};
+
+/// 选项名称列宽度
+pub const OPTION_LABEL_WIDTH: f32 = 240.0;
+/// 每次点击音量按钮增加的音量，超过 100% 后回到 0%
+pub const VOLUME_STEP: f32 = 0.1;
+/// 每次点击鼠标灵敏度按钮增加的灵敏度
+pub const SENSITIVITY_STEP: f32 = 0.5;
+/// 鼠标灵敏度的可选范围，超过最大值后回到最小值
+pub const SENSITIVITY_RANGE: (f32, f32) = (0.5, 3.0);
+
+/// 设置界面插件
+/// 注册设置界面的显示、交互和清理系统
+///
+/// # 说明
+/// 各项设置资源由 [`SettingsPlugin`](crate::SettingsPlugin) 插入
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Settings), setup_settings_menu)
+            .add_systems(
+                Update,
+                (handle_settings_input, update_option_labels)
+                    .run_if(in_state(GameState::Settings))
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::Settings), cleanup_settings_menu);
+    }
+}
+
+/// 设置界面根节点标记组件
+#[derive(Component)]
+pub struct SettingsMenuRoot;
+
+/// 设置界面中的选项，作为组件标记对应的选项值按钮
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsOption {
+    /// 主音量
+    MasterVolume,
+    /// 音效音量
+    SfxVolume,
+    /// 音乐音量
+    MusicVolume,
+    /// 窗口或全屏
+    DisplayMode,
+    /// 垂直同步
+    VSync,
+    /// 界面语言
+    Language,
+    /// 难度
+    Difficulty,
+    /// 输入设备
+    InputSource,
+    /// 鼠标灵敏度
+    MouseSensitivity,
+}
+
+impl SettingsOption {
+    /// 所有选项，设置界面按这个顺序显示
+    pub const ALL: [SettingsOption; 9] = [
+        SettingsOption::MasterVolume,
+        SettingsOption::SfxVolume,
+        SettingsOption::MusicVolume,
+        SettingsOption::DisplayMode,
+        SettingsOption::VSync,
+        SettingsOption::Language,
+        SettingsOption::Difficulty,
+        SettingsOption::InputSource,
+        SettingsOption::MouseSensitivity,
+    ];
+
+    /// 选项名称（中文原文，显示时通过 [`Language::tr`] 翻译）
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsOption::MasterVolume => "主音量",
+            SettingsOption::SfxVolume => "音效音量",
+            SettingsOption::MusicVolume => "音乐音量",
+            SettingsOption::DisplayMode => "显示模式",
+            SettingsOption::VSync => "垂直同步",
+            SettingsOption::Language => "语言",
+            SettingsOption::Difficulty => "难度",
+            SettingsOption::InputSource => "输入设备",
+            SettingsOption::MouseSensitivity => "鼠标灵敏度",
+        }
+    }
+}
+
+/// 设置界面底部按钮的操作类型
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsMenuButton {
+    /// 进入按键设置界面
+    Controls,
+    /// 返回主菜单
+    Back,
+}
+
+/// 初始化设置界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区，用于创建UI实体
+/// - `theme`: 界面主题
+/// - `audio`: 音量设置
+/// - `video`: 显示设置
+/// - `language`: 界面语言
+/// - `difficulty`: 难度
+/// - `input`: 输入设置
+///
+/// # 说明
+/// 每个选项一行，底部为按键设置和返回按钮
+pub fn setup_settings_menu(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    audio: Res<AudioSettings>,
+    video: Res<VideoSettings>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+    input: Res<InputSettings>,
+) {
+    commands
+        .spawn((widgets::screen_with_gap(theme.background, 10.0), SettingsMenuRoot))
+        .with_children(|parent| {
+            parent.spawn(widgets::title(&theme, language.tr("设置"), theme.title_color));
+
+            for option in SettingsOption::ALL {
+                parent.spawn(widgets::row(20.0)).with_children(|row| {
+                    row.spawn((
+                        widgets::label(&theme, language.tr(option.label()), theme.text_font_size, theme.title_color),
+                        Node {
+                            width: Val::Px(OPTION_LABEL_WIDTH),
+                            ..default()
+                        },
+                    ));
+                    row.spawn((
+                        widgets::button(
+                            &theme,
+                            option_value(option, &audio, &video, *language, *difficulty, &input),
+                            theme.menu_button,
+                            theme.small_button,
+                        ),
+                        option,
+                    ));
+                });
+            }
+
+            parent
+                .spawn(Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..widgets::row(30.0)
+                })
+                .with_children(|bar| {
+                    for (label, button) in [
+                        ("按键设置", SettingsMenuButton::Controls),
+                        ("返回", SettingsMenuButton::Back),
+                    ] {
+                        bar.spawn((
+                            widgets::button(&theme, language.tr(label), theme.menu_button, theme.button),
+                            button,
+                        ));
+                    }
+                });
+        });
+}
+
+/// 处理设置界面输入系统
+///
+/// # 参数
+/// - `option_query`: 查询选项值按钮的交互状态
+/// - `button_query`: 查询底部按钮的交互状态
+/// - `menu_input`: 界面操作资源
+/// - `audio`: 音量设置
+/// - `video`: 显示设置
+/// - `language`: 界面语言
+/// - `difficulty`: 难度
+/// - `input`: 输入设置
+/// - `next_state`: 下一个游戏状态
+///
+/// # 功能
+/// - 点击选项值按钮时切换到下一个值，切换语言后重新进入设置界面，以新语言重建界面
+/// - 点击按键设置时进入按键设置界面
+/// - 点击返回或按下返回键（手柄 B 键）时回到主菜单
+pub fn handle_settings_input(
+    option_query: Query<(&Interaction, &SettingsOption), Changed<Interaction>>,
+    button_query: Query<(&Interaction, &SettingsMenuButton), Changed<Interaction>>,
+    menu_input: Res<MenuInput>,
+    mut audio: ResMut<AudioSettings>,
+    mut video: ResMut<VideoSettings>,
+    mut language: ResMut<Language>,
+    mut difficulty: ResMut<Difficulty>,
+    mut input: ResMut<InputSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, option) in &option_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match option {
+            SettingsOption::MasterVolume => audio.master = next_volume(audio.master),
+            SettingsOption::SfxVolume => audio.sfx = next_volume(audio.sfx),
+            SettingsOption::MusicVolume => audio.music = next_volume(audio.music),
+            SettingsOption::DisplayMode => video.fullscreen = !video.fullscreen,
+            SettingsOption::VSync => video.vsync = !video.vsync,
+            SettingsOption::Language => {
+                *language = next_in(&Language::ALL, *language);
+                next_state.set(GameState::Settings);
+            }
+            SettingsOption::Difficulty => *difficulty = next_in(&Difficulty::ALL, *difficulty),
+            SettingsOption::InputSource => input.source = next_in(&InputSource::ALL, input.source),
+            SettingsOption::MouseSensitivity => {
+                input.mouse_sensitivity = next_sensitivity(input.mouse_sensitivity);
+            }
+        }
+    }
+
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            SettingsMenuButton::Controls => next_state.set(GameState::Controls),
+            SettingsMenuButton::Back => next_state.set(GameState::Menu),
+        }
+    }
+
+    if menu_input.back {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// 更新选项值按钮文本系统
+///
+/// # 参数
+/// - `option_query`: 查询选项值按钮对应的选项
+/// - `label_query`: 查询按钮文字及其所属的按钮
+/// - `audio`: 音量设置
+/// - `video`: 显示设置
+/// - `language`: 界面语言
+/// - `difficulty`: 难度
+/// - `input`: 输入设置
+///
+/// # 说明
+/// 任意设置被修改后刷新所有选项值
+pub fn update_option_labels(
+    option_query: Query<&SettingsOption>,
+    mut label_query: Query<(&ChildOf, &mut Text), With<ButtonLabel>>,
+    audio: Res<AudioSettings>,
+    video: Res<VideoSettings>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+    input: Res<InputSettings>,
+) {
+    if !(audio.is_changed() || video.is_changed() || difficulty.is_changed() || input.is_changed()) {
+        return;
+    }
+    for (child_of, mut text) in &mut label_query {
+        if let Ok(option) = option_query.get(child_of.parent()) {
+            text.0 = option_value(*option, &audio, &video, *language, *difficulty, &input);
+        }
+    }
+}
+
+/// 清理设置界面系统
+///
+/// # 参数
+/// - `commands`: 命令缓冲区
+/// - `root_query`: 设置界面根节点
+pub fn cleanup_settings_menu(mut commands: Commands, root_query: Query<Entity, With<SettingsMenuRoot>>) {
+    for entity in &root_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 选项当前值的显示文本
+fn option_value(
+    option: SettingsOption,
+    audio: &AudioSettings,
+    video: &VideoSettings,
+    language: Language,
+    difficulty: Difficulty,
+    input: &InputSettings,
+) -> String {
+    let on_off = |on: bool| language.tr(if on { "开" } else { "关" }).to_string();
+    let percent = |volume: f32| format!("{:.0}%", volume * 100.0);
+    match option {
+        SettingsOption::MasterVolume => percent(audio.master),
+        SettingsOption::SfxVolume => percent(audio.sfx),
+        SettingsOption::MusicVolume => percent(audio.music),
+        SettingsOption::DisplayMode => language.tr(if video.fullscreen { "全屏" } else { "窗口" }).to_string(),
+        SettingsOption::VSync => on_off(video.vsync),
+        SettingsOption::Language => language.label().to_string(),
+        SettingsOption::Difficulty => language.tr(difficulty.label()).to_string(),
+        SettingsOption::InputSource => language.tr(input.source.label()).to_string(),
+        SettingsOption::MouseSensitivity => format!("{:.1}", input.mouse_sensitivity),
+    }
+}
+
+/// 列表中的下一个值，最后一个值之后回到第一个
+fn next_in<T: Copy + PartialEq>(values: &[T], current: T) -> T {
+    let index = values.iter().position(|value| *value == current).unwrap_or(0);
+    values[(index + 1) % values.len()]
+}
+
+/// 下一档音量：对齐到 10% 的整数倍后增加一档，超过 100% 后回到 0%
+fn next_volume(volume: f32) -> f32 {
+    let steps = (1.0 / VOLUME_STEP).round();
+    let next = (volume / VOLUME_STEP).round() + 1.0;
+    if next > steps { 0.0 } else { next * VOLUME_STEP }
+}
+
+/// 下一档鼠标灵敏度：对齐到 0.5 的整数倍后增加一档，超过最大值后回到最小值
+fn next_sensitivity(sensitivity: f32) -> f32 {
+    let (min, max) = SENSITIVITY_RANGE;
+    let next = ((sensitivity / SENSITIVITY_STEP).round() + 1.0) * SENSITIVITY_STEP;
+    if next > max || next < min { min } else { next }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_values_wrap_around() {
+        assert!((next_volume(0.8) - 0.9).abs() < 1e-6);
+        assert_eq!(next_volume(1.0), 0.0);
+        assert_eq!(next_sensitivity(1.5), 2.0);
+        assert_eq!(next_sensitivity(3.0), 0.5);
+        assert_eq!(next_in(&Difficulty::ALL, Difficulty::Hard), Difficulty::Easy);
+    }
+}
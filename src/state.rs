@@ -9,27 +9,29 @@ pub enum GameState {
     Menu,
     /// 关卡选择状态 - 显示关卡选择界面
     LevelSelect,
+    /// 设置状态 - 显示设置界面
+    Settings,
+    /// 按键设置状态 - 显示按键设置界面（从设置界面进入）
+    Controls,
     /// 游戏进行中状态
     Playing,
     /// 游戏结束状态（失败）
     GameOver,
     /// 游戏胜利状态（所有砖块被消灭）
     Victory,
+    /// 战役成绩状态（战役模式通关所有关卡后显示）
+    CampaignResults,
 }
 
 /// 当前关卡资源
-/// 存储玩家选择的关卡编号
+/// 存储玩家选择的关卡在 [`LevelCatalog`](crate::levels::LevelCatalog) 中的索引
 #[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum Level {
-    /// 第一关 - 简单难度，只有3行砖块
-    #[default]
-    Level1,
-    /// 第二关 - 完整难度，填满砖块
-    Level2,
-}
+pub struct Level(pub usize);
 
 /// 游戏进行中状态枚举
 /// 定义游戏进行中的不同状态，用于状态管理
+// 变体名与 BallAttached 组件重名，因此保留小写命名
+#[allow(non_camel_case_types)]
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum PlayingState {
     /// 初始小球附着在挡板上
@@ -37,4 +39,18 @@ pub enum PlayingState {
     ball_attached,
     /// 发射后游戏进行中状态
     ball_launched,
-}
\ No newline at end of file
+}
+/// 暂停状态枚举
+/// 是 [`GameState::Playing`] 的子状态，只在游戏进行中存在，离开游戏时自动移除
+///
+/// # 说明
+/// 暂停时虚拟时间（`Time<Virtual>`）被冻结，固定时间步长的物理系统和游戏输入都不再运行
+#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    /// 游戏正常运行
+    #[default]
+    Running,
+    /// 游戏已暂停，显示暂停菜单
+    Paused,
+}
@@ -0,0 +1,281 @@
+//! 界面焦点模块
+//! 让所有使用 `Button` 的界面都可以用键盘或手柄操作：方向键/十字键在按钮之间移动焦点，
+//! 确认键/手柄 A 键按下获得焦点的按钮
+//!
+//! - 获得焦点的按钮被设置为 [`Interaction::Hovered`]，各界面原有的悬停颜色即为焦点高亮
+//! - 确认时把按钮设置为 [`Interaction::Pressed`]，各界面原有的点击处理无需修改
+//! - 移动鼠标后焦点跟随鼠标悬停的按钮，再次使用键盘或手柄时恢复焦点高亮
+//! - 有自己的导航方式的界面（例如关卡选择、按键捕获、名字输入）通过
+//!   `configure_sets(PreUpdate, UiFocusSystems.run_if(..))` 暂停焦点导航
+
+use std::mem;
+
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use crate::actions::{Action, KeyBindings};
+
+/// 手柄摇杆超过这个偏移量时视为按下对应方向
+pub const STICK_NAVIGATION_THRESHOLD: f32 = 0.5;
+
+/// 界面焦点插件
+/// 每帧汇总键盘和手柄的界面操作，并在按钮之间移动焦点
+pub struct UiFocusPlugin;
+
+impl Plugin for UiFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuInput>()
+            .init_resource::<UiFocus>()
+            .add_systems(PreUpdate, read_menu_input.after(InputSystems))
+            .add_systems(
+                PreUpdate,
+                update_ui_focus
+                    .in_set(UiFocusSystems)
+                    .after(read_menu_input)
+                    // 在界面交互状态更新之后运行，覆盖鼠标产生的交互状态
+                    .after(UiSystems::Focus),
+            );
+    }
+}
+
+/// 焦点导航系统集，其他插件可以为它添加运行条件以暂停焦点导航
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UiFocusSystems;
+
+/// 界面操作资源：本帧刚按下的界面操作（键盘和所有手柄合并）
+///
+/// # 说明
+/// 上下左右为方向键、向左/向右移动键、手柄十字键或左摇杆；
+/// 确认为确认键或手柄 South 键（Xbox 的 A 键）；返回为返回键或手柄 East 键（Xbox 的 B 键）
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MenuInput {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub confirm: bool,
+    pub back: bool,
+}
+
+impl MenuInput {
+    /// 本帧按下的方向（界面坐标，y 轴向下），没有按下方向时为 None
+    pub fn direction(&self) -> Option<Vec2> {
+        let x = self.right as i32 - self.left as i32;
+        let y = self.down as i32 - self.up as i32;
+        (x != 0 || y != 0).then(|| Vec2::new(x as f32, y as f32))
+    }
+}
+
+/// 界面焦点资源
+#[derive(Resource, Debug, Default)]
+pub struct UiFocus {
+    /// 当前获得焦点的按钮
+    pub focused: Option<Entity>,
+    /// 是否正在使用键盘或手柄导航，移动鼠标后为 false
+    pub navigating: bool,
+    /// 上一帧通过确认键按下的按钮，本帧恢复为悬停状态
+    pressed: Option<Entity>,
+}
+
+/// 汇总界面操作系统（每帧在输入更新后运行）
+///
+/// # 参数
+/// - `keyboard_input`: 键盘输入资源
+/// - `bindings`: 按键绑定
+/// - `gamepads`: 已连接的手柄
+/// - `stick_direction`: 上一帧左摇杆所在的方向，摇杆进入新方向时才算一次按下
+/// - `menu_input`: 界面操作资源
+pub fn read_menu_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    mut stick_direction: Local<IVec2>,
+    mut menu_input: ResMut<MenuInput>,
+) {
+    let key = |key: KeyCode| keyboard_input.just_pressed(key);
+    let action = |action: Action| bindings.just_pressed(action, &keyboard_input);
+    let button = |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    // 摇杆取第一个有偏移的手柄，量化为八个方向
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| gamepad.left_stick())
+        .find(|stick| stick.abs().max_element() > STICK_NAVIGATION_THRESHOLD)
+        .unwrap_or(Vec2::ZERO);
+    let quantize = |value: f32| {
+        if value > STICK_NAVIGATION_THRESHOLD {
+            1
+        } else if value < -STICK_NAVIGATION_THRESHOLD {
+            -1
+        } else {
+            0
+        }
+    };
+    // 摇杆向上为正，界面 y 轴向下
+    let direction = IVec2::new(quantize(stick.x), -quantize(stick.y));
+    let previous = mem::replace(&mut *stick_direction, direction);
+    let stick_moved = |axis: i32, previous_axis: i32, sign: i32| axis == sign && previous_axis != sign;
+
+    *menu_input = MenuInput {
+        up: key(KeyCode::ArrowUp)
+            || button(GamepadButton::DPadUp)
+            || stick_moved(direction.y, previous.y, -1),
+        down: key(KeyCode::ArrowDown)
+            || button(GamepadButton::DPadDown)
+            || stick_moved(direction.y, previous.y, 1),
+        left: key(KeyCode::ArrowLeft)
+            || action(Action::MoveLeft)
+            || button(GamepadButton::DPadLeft)
+            || stick_moved(direction.x, previous.x, -1),
+        right: key(KeyCode::ArrowRight)
+            || action(Action::MoveRight)
+            || button(GamepadButton::DPadRight)
+            || stick_moved(direction.x, previous.x, 1),
+        confirm: action(Action::Confirm) || button(GamepadButton::South),
+        back: action(Action::Back) || button(GamepadButton::East),
+    };
+}
+
+/// 更新界面焦点系统（每帧在界面交互状态更新后运行）
+///
+/// # 参数
+/// - `menu_input`: 界面操作资源
+/// - `mouse_motion`: 本帧鼠标移动量，鼠标移动后焦点跟随鼠标
+/// - `focus`: 界面焦点资源
+/// - `buttons`: 查询所有按钮的交互状态、界面位置和可见性
+///
+/// # 逻辑
+/// 1. 获得焦点的按钮被销毁或隐藏后清除焦点
+/// 2. 上一帧通过确认键按下的按钮恢复为悬停状态
+/// 3. 鼠标移动时退出导航，焦点移到鼠标悬停的按钮
+/// 4. 按下方向时进入导航，焦点移到该方向上最近的按钮；没有焦点时选中第一个按钮
+/// 5. 按下确认时按下获得焦点的按钮
+/// 6. 导航时只有获得焦点的按钮显示为悬停状态
+pub fn update_ui_focus(
+    menu_input: Res<MenuInput>,
+    mouse_motion: Option<Res<AccumulatedMouseMotion>>,
+    mut focus: ResMut<UiFocus>,
+    mut buttons: Query<(Entity, &mut Interaction, &UiGlobalTransform, &InheritedVisibility), With<Button>>,
+) {
+    let focus_lost = focus
+        .focused
+        .is_some_and(|entity| !buttons.get(entity).is_ok_and(|(.., visibility)| visibility.get()));
+    if focus_lost {
+        focus.focused = None;
+    }
+
+    if let Some(entity) = focus.pressed.take()
+        && let Ok((_, mut interaction, _, _)) = buttons.get_mut(entity)
+        && *interaction == Interaction::Pressed
+    {
+        *interaction = Interaction::Hovered;
+    }
+
+    if mouse_motion.is_some_and(|motion| motion.delta != Vec2::ZERO) {
+        focus.navigating = false;
+        if let Some((entity, ..)) = buttons
+            .iter()
+            .find(|(_, interaction, _, visibility)| **interaction != Interaction::None && visibility.get())
+        {
+            focus.focused = Some(entity);
+        }
+    }
+
+    // 按钮的位置（界面坐标中心点），只包含可见的按钮
+    let positions: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .filter(|(_, _, _, visibility)| visibility.get())
+        .map(|(entity, _, transform, _)| (entity, transform.translation))
+        .collect();
+    if positions.is_empty() {
+        return;
+    }
+
+    if let Some(direction) = menu_input.direction() {
+        focus.navigating = true;
+        focus.focused = match focus.focused.and_then(|entity| positions.iter().find(|(e, _)| *e == entity)) {
+            Some(&(entity, from)) => Some(nearest_in_direction(&positions, from, direction).unwrap_or(entity)),
+            None => first_button(&positions),
+        };
+    } else if focus.navigating && focus.focused.is_none() {
+        // 导航时打开新界面，自动选中第一个按钮
+        focus.focused = first_button(&positions);
+    }
+
+    if menu_input.confirm {
+        match focus.focused {
+            Some(entity) => {
+                if let Ok((_, mut interaction, _, _)) = buttons.get_mut(entity) {
+                    *interaction = Interaction::Pressed;
+                    focus.pressed = Some(entity);
+                }
+            }
+            None => focus.focused = first_button(&positions),
+        }
+        focus.navigating = true;
+    }
+
+    if focus.navigating {
+        for (entity, mut interaction, _, _) in &mut buttons {
+            if Some(entity) == focus.focused {
+                if *interaction == Interaction::None {
+                    *interaction = Interaction::Hovered;
+                }
+            } else if *interaction == Interaction::Hovered {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+}
+
+/// 界面中的第一个按钮（最上方的一行中最左侧的按钮）
+fn first_button(positions: &[(Entity, Vec2)]) -> Option<Entity> {
+    positions
+        .iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .map(|(entity, _)| *entity)
+}
+
+/// 从 `from` 出发，在 `direction` 方向上最近的按钮
+///
+/// # 说明
+/// 只考虑位于该方向上的按钮，偏离方向的距离按两倍计算，优先选择同一行或同一列的按钮
+fn nearest_in_direction(positions: &[(Entity, Vec2)], from: Vec2, direction: Vec2) -> Option<Entity> {
+    let direction = direction.normalize();
+    positions
+        .iter()
+        .filter_map(|(entity, position)| {
+            let offset = *position - from;
+            let along = offset.dot(direction);
+            // 忽略自身和反方向的按钮
+            if along <= 1.0 {
+                return None;
+            }
+            let across = offset.perp_dot(direction).abs();
+            Some((*entity, along + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigation_prefers_buttons_in_the_same_column() {
+        let [a, b, c, d] = [0, 1, 2, 3].map(Entity::from_raw_u32).map(Option::unwrap);
+        // a 在上方，b 在 a 正下方，c 在 a 右下方较近处，d 在 a 右侧
+        let positions = [
+            (a, Vec2::new(100.0, 100.0)),
+            (b, Vec2::new(100.0, 200.0)),
+            (c, Vec2::new(180.0, 180.0)),
+            (d, Vec2::new(300.0, 100.0)),
+        ];
+        assert_eq!(nearest_in_direction(&positions, positions[0].1, Vec2::Y), Some(b));
+        assert_eq!(nearest_in_direction(&positions, positions[0].1, Vec2::X), Some(d));
+        assert_eq!(nearest_in_direction(&positions, positions[0].1, -Vec2::Y), None);
+        assert_eq!(first_button(&positions), Some(a));
+    }
+}
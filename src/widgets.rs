@@ -0,0 +1,299 @@
+//! 界面组件模块
+//! 所有界面共用的主题、按钮和文字组件，以及统一处理按钮悬停/按下颜色的系统
+//!
+//! 新界面用 [`screen`]、[`title`]、[`label`]、[`button`] 声明界面结构，
+//! 再给按钮加上表示操作的组件（通常是一个枚举），在自己的系统中处理 [`Interaction::Pressed`] 即可。
+//! 按钮的颜色由 [`ButtonColors`] 组件决定，修改这个组件即可改变按钮的外观（例如选中、未解锁）
+
+use bevy::prelude::*;
+
+/// 界面字体路径
+pub const FONT_PATH: &str = "fonts/songti.ttf";
+/// 全屏界面中子元素的默认间距
+pub const SCREEN_ROW_GAP: f32 = 20.0;
+
+/// 界面组件插件
+/// 插入界面主题资源，并注册统一的按钮颜色系统
+pub struct WidgetPlugin;
+
+impl Plugin for WidgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>()
+            // 在各界面的 Update 系统修改按钮颜色之后运行，同一帧内生效
+            .add_systems(PostUpdate, update_button_colors);
+    }
+}
+
+/// 按钮在各交互状态下的背景颜色
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ButtonColors {
+    /// 默认颜色
+    pub normal: Color,
+    /// 鼠标悬停或获得焦点时的颜色
+    pub hovered: Color,
+    /// 按下时的颜色
+    pub pressed: Color,
+}
+
+impl ButtonColors {
+    /// 所有交互状态都使用同一种颜色
+    pub const fn solid(color: Color) -> Self {
+        Self {
+            normal: color,
+            hovered: color,
+            pressed: color,
+        }
+    }
+
+    /// 交互状态对应的颜色
+    pub fn color(&self, interaction: Interaction) -> Color {
+        match interaction {
+            Interaction::None => self.normal,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
+        }
+    }
+}
+
+/// 按钮文字标记组件，由 [`button`] 添加在按钮的文字子实体上
+///
+/// # 说明
+/// 需要修改按钮文字时，查询带有这个组件的文字实体，再通过 [`ChildOf`] 找到所属的按钮
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ButtonLabel;
+
+/// 按钮尺寸和按钮文字的字体大小
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonSize {
+    /// 按钮尺寸
+    pub size: Vec2,
+    /// 按钮文字的字体大小
+    pub font_size: f32,
+}
+
+/// 界面主题资源
+///
+/// # 说明
+/// 菜单类界面（主菜单、关卡选择、设置）使用浅色背景和 `menu_button` 按钮；
+/// 显示在游戏画面上方的界面（暂停、游戏结束、胜利）使用半透明遮罩和 `overlay_button` 按钮
+#[derive(Resource, Debug, Clone)]
+pub struct UiTheme {
+    /// 界面字体
+    pub font: Handle<Font>,
+    /// 菜单类界面的背景颜色
+    pub background: Color,
+    /// 游戏画面上方界面的遮罩颜色
+    pub overlay: Color,
+    /// 菜单类界面的标题和文字颜色
+    pub title_color: Color,
+    /// 标题字体大小
+    pub title_font_size: f32,
+    /// 正文字体大小（设置项名称、排行榜）
+    pub text_font_size: f32,
+    /// 小号文字字体大小（关卡附加信息、页码）
+    pub small_font_size: f32,
+    /// 按钮文字颜色
+    pub button_text: Color,
+    /// 按钮悬停或按下时的文字颜色
+    pub button_text_hovered: Color,
+    /// 菜单类界面的按钮颜色
+    pub menu_button: ButtonColors,
+    /// 游戏画面上方界面的按钮颜色
+    pub overlay_button: ButtonColors,
+    /// 不可用按钮（未解锁关卡）的颜色
+    pub locked_button: ButtonColors,
+    /// 大按钮（主菜单）
+    pub large_button: ButtonSize,
+    /// 普通按钮
+    pub button: ButtonSize,
+    /// 小按钮（设置选项、按键绑定）
+    pub small_button: ButtonSize,
+    /// 关卡按钮
+    pub level_button: ButtonSize,
+    /// 翻页按钮
+    pub page_button: ButtonSize,
+}
+
+impl FromWorld for UiTheme {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            font: world.resource::<AssetServer>().load(FONT_PATH),
+            background: Color::srgb(0.9, 0.9, 0.9),
+            overlay: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            title_color: Color::srgb(0.2, 0.2, 0.6),
+            title_font_size: 60.0,
+            text_font_size: 26.0,
+            small_font_size: 24.0,
+            button_text: Color::WHITE,
+            button_text_hovered: Color::srgb(0.9, 0.9, 0.9),
+            menu_button: ButtonColors {
+                normal: Color::srgb(0.3, 0.3, 0.7),
+                hovered: Color::srgb(0.4, 0.4, 0.8),
+                pressed: Color::srgb(0.25, 0.25, 0.6),
+            },
+            overlay_button: ButtonColors {
+                normal: Color::srgb(0.3, 0.5, 0.8),
+                hovered: Color::srgb(0.4, 0.6, 0.9),
+                pressed: Color::srgb(0.25, 0.4, 0.7),
+            },
+            locked_button: ButtonColors::solid(Color::srgb(0.6, 0.6, 0.6)),
+            large_button: ButtonSize {
+                size: Vec2::new(300.0, 80.0),
+                font_size: 40.0,
+            },
+            button: ButtonSize {
+                size: Vec2::new(240.0, 60.0),
+                font_size: 30.0,
+            },
+            small_button: ButtonSize {
+                size: Vec2::new(260.0, 44.0),
+                font_size: 26.0,
+            },
+            level_button: ButtonSize {
+                size: Vec2::new(420.0, 60.0),
+                font_size: 40.0,
+            },
+            page_button: ButtonSize {
+                size: Vec2::new(120.0, 50.0),
+                font_size: 24.0,
+            },
+        }
+    }
+}
+
+impl UiTheme {
+    /// 使用界面字体的文字样式
+    pub fn text_font(&self, font_size: f32) -> TextFont {
+        TextFont {
+            font: self.font.clone(),
+            font_size,
+            ..default()
+        }
+    }
+}
+
+/// 全屏界面根节点：子元素垂直排列并居中，间距为 [`SCREEN_ROW_GAP`]
+///
+/// # 参数
+/// - `background`: 背景颜色，通常为 [`UiTheme::background`] 或 [`UiTheme::overlay`]
+pub fn screen(background: Color) -> impl Bundle {
+    screen_with_gap(background, SCREEN_ROW_GAP)
+}
+
+/// 指定子元素间距的全屏界面根节点，用于行数较多的界面
+pub fn screen_with_gap(background: Color, row_gap: f32) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(row_gap),
+            ..default()
+        },
+        BackgroundColor(background),
+    )
+}
+
+/// 水平排列子元素的一行
+pub fn row(column_gap: f32) -> Node {
+    Node {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(column_gap),
+        ..default()
+    }
+}
+
+/// 界面标题，下方留出间距
+pub fn title(theme: &UiTheme, text: impl Into<String>, color: Color) -> impl Bundle {
+    (
+        Text::new(text),
+        theme.text_font(theme.title_font_size),
+        TextColor(color),
+        Node {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        },
+    )
+}
+
+/// 一段文字
+pub fn label(theme: &UiTheme, text: impl Into<String>, font_size: f32, color: Color) -> impl Bundle {
+    (Text::new(text), theme.text_font(font_size), TextColor(color))
+}
+
+/// 固定尺寸、内容水平垂直居中的按钮布局
+pub fn button_node(size: Vec2) -> Node {
+    Node {
+        width: Val::Px(size.x),
+        height: Val::Px(size.y),
+        align_items: AlignItems::Center,
+        justify_content: JustifyContent::Center,
+        ..default()
+    }
+}
+
+/// 按钮外框（不含文字）
+///
+/// # 说明
+/// 需要自定义按钮内容或布局时使用，子元素由调用者生成；只有一行文字的按钮使用 [`button`]
+pub fn button_frame(colors: ButtonColors, node: Node) -> impl Bundle {
+    (Button, node, BackgroundColor(colors.normal), colors)
+}
+
+/// 带一行文字的按钮
+///
+/// # 说明
+/// 按钮文字带有 [`ButtonLabel`] 组件，需要修改文字时通过它查询
+pub fn button(theme: &UiTheme, text: impl Into<String>, colors: ButtonColors, size: ButtonSize) -> impl Bundle {
+    (
+        button_frame(colors, button_node(size.size)),
+        children![(label(theme, text, size.font_size, theme.button_text), ButtonLabel)],
+    )
+}
+
+/// 更新按钮颜色系统
+///
+/// # 参数
+/// - `buttons`: 查询交互状态或颜色发生变化的按钮
+/// - `text_colors`: 查询按钮文字颜色
+/// - `theme`: 界面主题
+///
+/// # 说明
+/// 背景颜色取自 [`ButtonColors`]，悬停或按下时按钮内所有文字使用 [`UiTheme::button_text_hovered`]
+pub fn update_button_colors(
+    mut buttons: Query<
+        (&Interaction, &ButtonColors, &mut BackgroundColor, Option<&Children>),
+        Or<(Changed<Interaction>, Changed<ButtonColors>)>,
+    >,
+    mut text_colors: Query<&mut TextColor>,
+    theme: Res<UiTheme>,
+) {
+    for (interaction, colors, mut background, children) in &mut buttons {
+        background.0 = colors.color(*interaction);
+        let text_color = match interaction {
+            Interaction::None => theme.button_text,
+            _ => theme.button_text_hovered,
+        };
+        for child in children.into_iter().flatten() {
+            if let Ok(mut color) = text_colors.get_mut(*child) {
+                color.0 = text_color;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solid_colors_ignore_interaction() {
+        let colors = ButtonColors::solid(Color::BLACK);
+        for interaction in [Interaction::None, Interaction::Hovered, Interaction::Pressed] {
+            assert_eq!(colors.color(interaction), Color::BLACK);
+        }
+    }
+}
@@ -0,0 +1,705 @@
+//! 游戏玩法集成测试：在无窗口的模拟器中运行物理和状态切换
+
+mod harness;
+
+use std::time::Duration;
+
+use bevy::time::TimeUpdateStrategy;
+use breakout::*;
+use harness::Harness;
+
+#[test]
+fn ball_starts_attached_and_launches_straight_up() {
+    let mut harness = Harness::new(&["#"]);
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.playing_state(), PlayingState::ball_attached);
+    assert_eq!(harness.remaining_bricks(), 1);
+    assert_eq!(harness.balls()[0].1, None);
+
+    harness.launch();
+    assert_eq!(harness.playing_state(), PlayingState::ball_launched);
+    let (_, velocity) = harness.balls()[0];
+    let velocity = velocity.expect("发射后小球应该有速度");
+    assert_eq!(velocity.x, 0.0);
+    assert!(velocity.y > 0.0);
+}
+
+#[test]
+fn breaking_the_last_brick_wins() {
+    let mut harness = Harness::new(&["#"]);
+    harness.launch();
+
+    let ticks = harness.run_until(200, |harness| harness.game_state() == GameState::Victory);
+    assert!(ticks.is_some(), "小球应该击中正上方的砖块");
+    assert_eq!(harness.remaining_bricks(), 0);
+
+    // 砖块得分加上通关奖励：3 条剩余生命，几秒内通关
+    let bonus = *harness.app.world().resource::<ClearBonus>();
+    assert_eq!(bonus.lives, 3 * LIFE_BONUS);
+    assert!(bonus.time > 0);
+    assert_eq!(harness.score(), BrickKind::Normal.points() + bonus.total());
+}
+
+#[test]
+fn consecutive_bricks_build_a_multiplier_until_the_ball_touches_the_paddle() {
+    let mut harness = Harness::new(&["#"]);
+    let destroyed = |kind| BrickDestroyed {
+        entity: Entity::PLACEHOLDER,
+        position: Vec2::ZERO,
+        kind,
+        speed: None,
+    };
+
+    // 前 COMBO_HITS_PER_MULTIPLIER 个砖块倍数为 1，之后倍数为 2
+    for _ in 0..COMBO_HITS_PER_MULTIPLIER {
+        harness.app.world_mut().trigger(destroyed(BrickKind::Normal));
+    }
+    harness.app.world_mut().trigger(destroyed(BrickKind::Explosive));
+    let expected = COMBO_HITS_PER_MULTIPLIER * BrickKind::Normal.points() + 2 * BrickKind::Explosive.points();
+    assert_eq!(harness.score(), expected);
+    assert_eq!(harness.app.world().resource::<Combo>().multiplier(), 2);
+
+    // 小球碰到挡板后连击中断，倍数恢复为 1
+    harness.app.world_mut().trigger(BallHitPaddle {
+        offset: 0.0,
+        speed: BALL_SPEED,
+        position: Vec2::ZERO,
+    });
+    assert_eq!(harness.app.world().resource::<Combo>().0, 0);
+    harness.app.world_mut().trigger(destroyed(BrickKind::Tough(3)));
+    assert_eq!(harness.score(), expected + BrickKind::Tough(3).points());
+}
+
+#[test]
+fn steel_bricks_do_not_count_towards_victory() {
+    let mut harness = Harness::new(&["S"]);
+    assert_eq!(harness.remaining_bricks(), 0);
+    harness.launch();
+
+    harness.run(120);
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.score(), 0);
+}
+
+#[test]
+fn losing_the_ball_costs_a_life() {
+    let mut harness = Harness::new(&["#"]);
+    harness.launch();
+
+    // 让小球从挡板下方落向底部墙壁
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+
+    let ticks = harness.run_until(60, |harness| harness.lives() == 2);
+    assert!(ticks.is_some(), "小球掉落后应该失去一条生命");
+    harness.tick();
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.playing_state(), PlayingState::ball_attached);
+    // 重新生成一个附着在挡板上的小球
+    let balls = harness.balls();
+    assert_eq!(balls.len(), 1);
+    assert_eq!(balls[0].1, None);
+}
+
+#[test]
+fn losing_the_last_life_ends_the_game() {
+    let mut harness = Harness::new(&["#"]);
+    harness.set_lives(1);
+    harness.launch();
+
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+
+    let ticks = harness.run_until(60, |harness| harness.game_state() == GameState::GameOver);
+    assert!(ticks.is_some(), "最后一条生命用完后应该进入游戏结束状态");
+    assert_eq!(harness.lives(), 0);
+}
+
+#[test]
+fn paddle_moves_and_stays_inside_walls() {
+    let mut harness = Harness::new(&["#"]);
+    let start = harness.paddle_position();
+
+    harness.press(KeyCode::ArrowLeft);
+    harness.run(10);
+    assert!(harness.paddle_position().x < start.x);
+
+    harness.run(300);
+    let left_bound = LEFT_WALL + WALL_THICKNESS / 2.0 + PADDLE_SIZE.x / 2.0 + PADDLE_PADDING;
+    assert!((harness.paddle_position().x - left_bound).abs() < 1e-3);
+
+    // 附着的小球跟随挡板移动
+    assert!((harness.balls()[0].0.x - left_bound).abs() < 1e-3);
+}
+
+#[test]
+fn ball_does_not_tunnel_through_bricks_on_long_steps() {
+    let mut harness = Harness::new(&["S"]);
+    harness.launch();
+
+    // 模拟一次卡顿：一个步长（虚拟时间单帧最多 250 毫秒）内小球移动 100 像素，
+    // 起点和终点都不与砖块（y = 30..60）重叠，离散检测会直接从砖块下方跳到砖块上方
+    harness.place_ball(Vec2::new(0.0, -20.0), Vec2::new(0.0, BALL_SPEED));
+    harness.set_tick(Duration::from_millis(250));
+    harness.tick();
+
+    let (position, velocity) = harness.balls()[0];
+    assert!(velocity.unwrap().y < 0.0, "小球应该被砖块反弹");
+    assert!(position.y < 30.0, "小球不应该穿过砖块");
+}
+
+#[test]
+fn pausing_freezes_ball_and_paddle() {
+    let mut harness = Harness::new(&["S"]);
+    harness.launch();
+    harness.run(5);
+
+    harness.tap(KeyCode::Escape);
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+    let ball = harness.balls()[0].0;
+    let paddle = harness.paddle_position();
+
+    harness.press(KeyCode::ArrowLeft);
+    harness.run(30);
+    assert_eq!(harness.balls()[0].0, ball, "暂停时小球不应该移动");
+    assert_eq!(harness.paddle_position(), paddle, "暂停时挡板不应该移动");
+    harness.release(KeyCode::ArrowLeft);
+
+    harness.tap(KeyCode::KeyP);
+    assert_eq!(harness.pause_state(), Some(PauseState::Running));
+    harness.run(5);
+    assert_ne!(harness.balls()[0].0, ball, "继续游戏后小球应该恢复移动");
+}
+
+#[test]
+fn losing_window_focus_pauses_the_game() {
+    let mut harness = Harness::new(&["#"]);
+    harness.launch();
+
+    harness.set_focus(false);
+    harness.run(2);
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+
+    // 重新获得焦点不会自动继续
+    harness.set_focus(true);
+    harness.run(2);
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+    assert_eq!(harness.lives(), 3);
+}
+
+#[test]
+fn saved_run_is_restored_on_resume() {
+    let mut harness = Harness::new(&["3#", "S#"]);
+    harness.press(KeyCode::ArrowRight);
+    harness.run(20);
+    harness.release(KeyCode::ArrowRight);
+    harness.tick();
+
+    let mut snapshot = harness.capture();
+    assert_eq!(snapshot.bricks.len(), 4);
+    assert!(!snapshot.ball_launched);
+    // 模拟一局打到一半的游戏：坚固砖块受损、一个普通砖块已被摧毁
+    snapshot.score = 7;
+    snapshot.lives = 2;
+    snapshot.combo = 4;
+    snapshot.elapsed_secs = 30.0;
+    snapshot.bricks.retain(|brick| brick.kind != BrickKind::Normal || brick.position.y > 60.0);
+    for brick in &mut snapshot.bricks {
+        if brick.kind == BrickKind::Tough(3) {
+            brick.health = Some(1);
+        }
+    }
+
+    harness.resume(snapshot.clone());
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.score(), 7);
+    assert_eq!(harness.lives(), 2);
+    // 坚固砖块和剩下的一个普通砖块
+    assert_eq!(harness.remaining_bricks(), 2);
+    assert_eq!(harness.paddle_position(), snapshot.paddle);
+
+    let mut restored = harness.capture();
+    let by_position = |a: &SavedBrick, b: &SavedBrick| {
+        a.position.x.total_cmp(&b.position.x).then(a.position.y.total_cmp(&b.position.y))
+    };
+    restored.bricks.sort_by(by_position);
+    snapshot.bricks.sort_by(by_position);
+    // 恢复之后关卡计时继续走
+    assert!((30.0..30.5).contains(&restored.elapsed_secs));
+    restored.elapsed_secs = snapshot.elapsed_secs;
+    assert_eq!(restored, snapshot);
+}
+
+/// 让小球从挡板下方落出，直到最后一条生命用完
+fn lose_the_run(harness: &mut Harness) {
+    harness.set_lives(1);
+    harness.launch();
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+    harness.run_until(60, |harness| harness.game_state() == GameState::GameOver).unwrap();
+}
+
+#[test]
+fn only_the_run_that_owns_the_save_deletes_it() {
+    let mut harness = Harness::new(&["3#", "S#"]);
+    let mut other_run = harness.capture();
+    other_run.score = 99;
+    harness.set_saved_run(Some(other_run.clone()));
+
+    // 新开的一局结束时保留之前保存的另一局
+    lose_the_run(&mut harness);
+    assert_eq!(harness.saved_run(), Some(other_run.clone()));
+
+    // 从存档继续的一局结束时删除存档
+    harness.resume(other_run);
+    lose_the_run(&mut harness);
+    assert_eq!(harness.saved_run(), None);
+
+    // 暂停时写入存档的一局结束时删除存档
+    harness.set_state(GameState::Menu);
+    harness.set_state(GameState::Playing);
+    harness.tap(KeyCode::Escape);
+    assert!(harness.saved_run().is_some(), "暂停时应该保存存档");
+    harness.tap(KeyCode::Escape);
+    lose_the_run(&mut harness);
+    assert_eq!(harness.saved_run(), None);
+}
+
+#[test]
+fn replay_reproduces_the_run_at_a_different_frame_rate() {
+    let grid = ["#3#3#3#3", "########", "S#E##E#S"];
+    let mut recorded = Harness::new(&grid);
+    recorded.press(KeyCode::ArrowLeft);
+    recorded.run(25);
+    recorded.release(KeyCode::ArrowLeft);
+    recorded.launch();
+    recorded.run(60);
+    recorded.press(KeyCode::ArrowRight);
+    recorded.run(40);
+    recorded.release(KeyCode::ArrowRight);
+    recorded.run(300);
+    // 回放时每帧运行两个固定时间步长，录像的步数需要是偶数
+    recorded.run_until(2, |harness| harness.recording().ticks() % 2 == 0).unwrap();
+    assert_eq!(recorded.game_state(), GameState::Playing);
+    let replay = recorded.recording();
+    let expected = recorded.capture();
+    assert!(expected.score > 0, "录制的这局应该摧毁了砖块");
+
+    // 回放时帧率减半，每帧运行两个固定时间步长
+    let mut replayed = Harness::new(&grid);
+    replayed
+        .app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(harness::TICK * 2));
+    let ticks = replay.ticks();
+    replayed.play_back(replay);
+    replayed.run_until(1000, |harness| harness.playback_tick() >= Some(ticks)).unwrap();
+    assert_eq!(replayed.playback_tick(), Some(ticks));
+    assert_eq!(replayed.capture(), expected);
+}
+
+#[test]
+fn replay_playback_leaves_saves_and_high_scores_alone() {
+    let mut harness = Harness::new(&["#.#.#"]);
+    let saved = harness.capture();
+    harness.set_saved_run(Some(saved.clone()));
+    let recording = harness.recording();
+
+    // 发射小球打掉正上方的砖块，然后一直向左移动，让反弹回来的小球落出挡板
+    let replay = Replay {
+        lives: 1,
+        inputs: vec![
+            (PlayerInput { direction: 0.0, fire: true }, 1),
+            (PlayerInput { direction: -1.0, fire: false }, 600),
+        ],
+        ..recording
+    };
+    harness.play_back(replay);
+    harness.tap(KeyCode::Escape);
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+    harness.tap(KeyCode::Escape);
+    harness.run_until(600, |harness| harness.game_state() == GameState::GameOver).unwrap();
+    assert!(harness.score() > 0, "回放的这局应该得分，足以进入空排行榜");
+
+    // 离开结束界面时会提交未确认的名字输入
+    harness.set_state(GameState::Menu);
+    assert_eq!(harness.saved_run(), Some(saved));
+    assert_eq!(harness.high_scores(), HighScores::default());
+    assert_eq!(harness.playback_tick(), None);
+}
+
+#[test]
+fn gamepad_stick_moves_paddle_proportionally_and_south_launches() {
+    let mut harness = Harness::new(&["#"]);
+    harness.set_input_settings(InputSettings {
+        source: InputSource::Gamepad,
+        ..default()
+    });
+    let gamepad = harness.connect_gamepad();
+    harness.tick();
+
+    // 半推摇杆，挡板以一半的速度移动
+    let start = harness.paddle_position().x;
+    harness.move_gamepad_axis(gamepad, GamepadAxis::LeftStickX, 0.5);
+    harness.run(10);
+    let moved = harness.paddle_position().x - start;
+    let expected = 0.5 * PADDLE_SPEED * harness::TICK.as_secs_f32() * 10.0;
+    assert!((moved - expected).abs() < 1.0, "挡板移动了 {moved}，预期约 {expected}");
+
+    // 选择手柄时键盘不再控制挡板
+    harness.move_gamepad_axis(gamepad, GamepadAxis::LeftStickX, 0.0);
+    harness.tick();
+    let stopped = harness.paddle_position().x;
+    harness.press(KeyCode::ArrowRight);
+    harness.run(5);
+    assert_eq!(harness.paddle_position().x, stopped);
+    harness.release(KeyCode::ArrowRight);
+
+    harness.set_gamepad_button(gamepad, GamepadButton::South, true);
+    harness.tick();
+    harness.set_gamepad_button(gamepad, GamepadButton::South, false);
+    harness.tick();
+    assert_eq!(harness.playing_state(), PlayingState::ball_launched);
+}
+
+#[test]
+fn mouse_follow_moves_paddle_toward_pointer_and_click_launches() {
+    let mut harness = Harness::new(&["#"]);
+    harness.set_input_settings(InputSettings {
+        source: InputSource::Mouse,
+        mouse_sensitivity: 2.0,
+    });
+
+    // 挡板追赶指针的最大速度是挡板速度的两倍
+    let start = harness.paddle_position().x;
+    harness.set_pointer(Some(start + 300.0));
+    harness.tick();
+    let step = harness.paddle_position().x - start;
+    let max_step = 2.0 * PADDLE_SPEED * harness::TICK.as_secs_f32();
+    assert!((step - max_step).abs() < 1e-3, "挡板移动了 {step}，预期 {max_step}");
+
+    // 追上指针后停在指针位置
+    harness.run(30);
+    assert!((harness.paddle_position().x - (start + 300.0)).abs() < 1e-3);
+
+    // 指针离开窗口时挡板停在原地
+    harness.set_pointer(None);
+    harness.run(5);
+    assert!((harness.paddle_position().x - (start + 300.0)).abs() < 1e-3);
+
+    harness.click();
+    assert_eq!(harness.playing_state(), PlayingState::ball_launched);
+}
+
+#[test]
+fn rebound_keys_replace_the_defaults() {
+    let mut harness = Harness::new(&["#"]);
+    harness.rebind(Action::MoveLeft, KeyCode::KeyA);
+    harness.rebind(Action::Pause, KeyCode::KeyQ);
+
+    // 原来的方向键不再移动挡板
+    let start = harness.paddle_position().x;
+    harness.press(KeyCode::ArrowLeft);
+    harness.run(5);
+    harness.release(KeyCode::ArrowLeft);
+    harness.tick();
+    assert_eq!(harness.paddle_position().x, start);
+
+    harness.press(KeyCode::KeyA);
+    harness.run(5);
+    harness.release(KeyCode::KeyA);
+    harness.tick();
+    assert!(harness.paddle_position().x < start);
+
+    // Esc 不再暂停，新的暂停键可以暂停
+    harness.tap(KeyCode::Escape);
+    assert_eq!(harness.pause_state(), Some(PauseState::Running));
+    harness.tap(KeyCode::KeyQ);
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+}
+
+/// 挡板的宽度
+fn paddle_width(harness: &mut Harness) -> f32 {
+    let world = harness.app.world_mut();
+    world.query_filtered::<&Transform, With<Paddle>>().single(world).unwrap().scale.x
+}
+
+/// 唯一一个小球的速度大小
+fn ball_speed(harness: &mut Harness) -> f32 {
+    let velocity = harness.balls()[0].1.expect("小球应该已经发射");
+    velocity.length()
+}
+
+#[test]
+fn difficulty_presets_apply_to_the_run() {
+    let mut harness = Harness::new(&["S"]);
+    harness.app.insert_resource(Difficulty::Hard);
+    harness.set_state(GameState::Menu);
+    harness.set_state(GameState::Playing);
+    assert_eq!(harness.lives(), Difficulty::Hard.starting_lives());
+    assert_eq!(harness.run_progress().difficulty, Difficulty::Hard);
+
+    harness.tick();
+    assert_eq!(paddle_width(&mut harness), PADDLE_SIZE.x * Difficulty::Hard.paddle_width_scale());
+    harness.launch();
+    harness.run(5);
+    assert!((ball_speed(&mut harness) - BALL_SPEED * Difficulty::Hard.ball_speed_scale()).abs() < 0.01);
+
+    // 游戏中修改难度设置不影响正在进行的这局，回到菜单后下一局才使用新的难度
+    harness.app.insert_resource(Difficulty::Easy);
+    harness.tick();
+    assert_eq!(harness.run_progress().difficulty, Difficulty::Hard);
+    harness.set_state(GameState::Menu);
+    harness.set_state(GameState::Playing);
+    assert_eq!(harness.lives(), 5);
+    harness.tick();
+    assert_eq!(paddle_width(&mut harness), PADDLE_SIZE.x * Difficulty::Easy.paddle_width_scale());
+}
+
+#[test]
+fn ball_speeds_up_with_paddle_hits_until_the_difficulty_cap() {
+    let mut harness = Harness::new(&["S"]);
+    harness.launch();
+    harness.run(5);
+    assert!((ball_speed(&mut harness) - BALL_SPEED).abs() < 0.01);
+
+    let hit = BallHitPaddle {
+        offset: 0.0,
+        speed: BALL_SPEED,
+        position: Vec2::ZERO,
+    };
+    for _ in 0..2 {
+        harness.app.world_mut().trigger(hit);
+    }
+    harness.tick();
+    let expected = BALL_SPEED * (1.0 + 2.0 * BALL_SPEED_UP_PER_HIT);
+    assert!((ball_speed(&mut harness) - expected).abs() < 0.01);
+
+    // 加速有上限
+    for _ in 0..1000 {
+        harness.app.world_mut().trigger(hit);
+    }
+    harness.tick();
+    let capped = BALL_SPEED * Difficulty::Normal.max_speed_scale();
+    assert!((ball_speed(&mut harness) - capped).abs() < 0.01);
+
+    // 失去生命后恢复初始速度
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+    let ticks = harness.run_until(60, |harness| harness.lives() == 2);
+    assert!(ticks.is_some(), "小球掉落后应该失去一条生命");
+    assert_eq!(harness.app.world().resource::<SpeedProgression>().0, 0);
+    harness.launch();
+    harness.run(5);
+    assert!((ball_speed(&mut harness) - BALL_SPEED).abs() < 0.01);
+}
+
+#[test]
+fn buttons_can_be_focused_and_pressed_without_a_mouse() {
+    let mut harness = Harness::new(&["#"]);
+    harness.set_state(GameState::Menu);
+    harness.app.add_plugins(UiFocusPlugin);
+    let top = harness.spawn_button(400.0, 100.0);
+    let middle = harness.spawn_button(400.0, 200.0);
+    let bottom = harness.spawn_button(400.0, 300.0);
+
+    // 第一次按方向键选中最上方的按钮
+    harness.tap(KeyCode::ArrowDown);
+    assert_eq!(harness.interaction(top), Interaction::Hovered);
+    harness.tap(KeyCode::ArrowDown);
+    assert_eq!(harness.interaction(top), Interaction::None);
+    assert_eq!(harness.interaction(middle), Interaction::Hovered);
+
+    // 手柄十字键同样可以移动焦点，确认键按下获得焦点的按钮
+    let gamepad = harness.connect_gamepad();
+    harness.set_gamepad_button(gamepad, GamepadButton::DPadDown, true);
+    harness.tick();
+    harness.set_gamepad_button(gamepad, GamepadButton::DPadDown, false);
+    harness.tick();
+    assert_eq!(harness.interaction(bottom), Interaction::Hovered);
+    harness.press(KeyCode::Enter);
+    harness.tick();
+    assert_eq!(harness.interaction(bottom), Interaction::Pressed);
+    harness.release(KeyCode::Enter);
+    harness.tick();
+    assert_eq!(harness.interaction(bottom), Interaction::Hovered);
+}
+
+#[test]
+fn gamepad_confirms_a_new_record_and_then_navigates_the_result_screen() {
+    let mut harness = Harness::new(&["#"]);
+    harness.app.add_plugins(UiFocusPlugin);
+    let gamepad = harness.connect_gamepad();
+    harness.app.world_mut().resource_mut::<Score>().0 = 7;
+    lose_the_run(&mut harness);
+    assert!(harness.app.world().contains_resource::<NameEntry>(), "分数应该能进入空排行榜");
+    let retry = harness.spawn_button(400.0, 100.0);
+
+    // 输入名字时十字键不移动焦点
+    harness.tap_gamepad_button(gamepad, GamepadButton::DPadDown);
+    assert_eq!(harness.interaction(retry), Interaction::None);
+
+    // South 键确认名字，记录以默认名字写入排行榜
+    harness.tap_gamepad_button(gamepad, GamepadButton::South);
+    assert!(!harness.app.world().contains_resource::<NameEntry>());
+    let table = harness.high_scores().table("test").to_vec();
+    assert_eq!(table.len(), 1);
+    assert_eq!((table[0].name.as_str(), table[0].score), (HIGH_SCORE_DEFAULT_NAME, 7));
+
+    // 之后手柄可以选中并按下结束界面的按钮
+    harness.tap_gamepad_button(gamepad, GamepadButton::DPadDown);
+    assert_eq!(harness.interaction(retry), Interaction::Hovered);
+    harness.set_gamepad_button(gamepad, GamepadButton::South, true);
+    harness.tick();
+    assert_eq!(harness.interaction(retry), Interaction::Pressed);
+}
+
+#[test]
+fn gamepad_start_toggles_pause() {
+    let mut harness = Harness::new(&["#"]);
+    let gamepad = harness.connect_gamepad();
+    harness.set_gamepad_button(gamepad, GamepadButton::Start, true);
+    harness.tick();
+    harness.set_gamepad_button(gamepad, GamepadButton::Start, false);
+    harness.tick();
+    assert_eq!(harness.pause_state(), Some(PauseState::Paused));
+}
+
+#[test]
+fn next_level_carries_the_score_and_retry_restores_it() {
+    let mut harness = Harness::new(&["#"]);
+    harness.add_level(&["#"]);
+    harness.launch();
+    let ticks = harness.run_until(200, |harness| harness.game_state() == GameState::Victory);
+    assert!(ticks.is_some(), "小球应该击中正上方的砖块");
+    let first_level_score = harness.score();
+    assert!(first_level_score > 0);
+    assert_eq!(harness.run_progress().banked_score(), first_level_score);
+
+    harness.run_system(
+        |mut level: ResMut<Level>,
+         catalog: Option<Res<LevelCatalog>>,
+         mut next_game_state: ResMut<NextState<GameState>>,
+         mut next_playing_state: ResMut<NextState<PlayingState>>| {
+            assert!(start_next_level(&mut level, catalog.as_deref(), &mut next_game_state, &mut next_playing_state));
+        },
+    );
+    harness.tick();
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.level(), 1);
+    assert_eq!(harness.score(), first_level_score);
+    assert_eq!(harness.remaining_bricks(), 1);
+
+    // 在第二关用完生命后重试：分数恢复为进入第二关时的分数
+    harness.set_lives(1);
+    harness.launch();
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+    let ticks = harness.run_until(60, |harness| harness.game_state() == GameState::GameOver);
+    assert!(ticks.is_some(), "最后一条生命用完后应该进入游戏结束状态");
+    harness.app.world_mut().resource_mut::<Score>().0 = first_level_score + 7;
+
+    harness.run_system(
+        |mut score: ResMut<Score>,
+         mut lives: ResMut<Lives>,
+         run: Res<RunProgress>,
+         mut next_game_state: ResMut<NextState<GameState>>,
+         mut next_playing_state: ResMut<NextState<PlayingState>>| {
+            retry_level(&mut score, &mut lives, &run, &mut next_game_state, &mut next_playing_state);
+        },
+    );
+    harness.tick();
+    assert_eq!(harness.game_state(), GameState::Playing);
+    assert_eq!(harness.level(), 1);
+    assert_eq!(harness.score(), first_level_score);
+    assert_eq!(harness.lives(), Difficulty::default().starting_lives());
+    assert_eq!(harness.remaining_bricks(), 1);
+
+    // 回到菜单时本局游戏进度被清空
+    harness.set_state(GameState::Menu);
+    assert_eq!(harness.run_progress(), RunProgress::default());
+}
+
+#[test]
+fn level_high_scores_exclude_points_carried_from_earlier_levels() {
+    let mut harness = Harness::new(&["#"]);
+    harness.add_level(&["#"]);
+    harness.launch();
+    harness.run_until(200, |harness| harness.game_state() == GameState::Victory).unwrap();
+    let first_level_score = harness.score();
+
+    harness.run_system(
+        |mut level: ResMut<Level>,
+         catalog: Option<Res<LevelCatalog>>,
+         mut next_game_state: ResMut<NextState<GameState>>,
+         mut next_playing_state: ResMut<NextState<PlayingState>>| {
+            assert!(start_next_level(&mut level, catalog.as_deref(), &mut next_game_state, &mut next_playing_state));
+        },
+    );
+    harness.tick();
+    harness.launch();
+    harness.run_until(200, |harness| harness.game_state() == GameState::Victory).unwrap();
+    let second_level_score = harness.score() - first_level_score;
+    assert!(second_level_score > 0);
+
+    // 离开胜利界面时提交名字输入
+    harness.set_state(GameState::Menu);
+    let high_scores = harness.high_scores();
+    assert_eq!(high_scores.best("test"), Some(first_level_score));
+    assert_eq!(high_scores.best("test 2"), Some(second_level_score));
+}
+
+/// 游戏中触发的事件
+#[derive(Resource, Default)]
+struct Heard(Vec<&'static str>);
+
+/// 记录游戏中触发的事件
+fn listen(harness: &mut Harness) {
+    harness
+        .app
+        .init_resource::<Heard>()
+        .add_observer(|_: On<BallLaunched>, mut heard: ResMut<Heard>| heard.0.push("launch"))
+        .add_observer(|hit: On<BallHitPaddle>, mut heard: ResMut<Heard>| {
+            // 小球从挡板中心垂直发射，反弹回来也落在挡板中心
+            assert!(hit.offset.abs() < 0.01);
+            assert!(hit.speed > 0.0);
+            heard.0.push("paddle")
+        })
+        .add_observer(|_: On<BallHitWall>, mut heard: ResMut<Heard>| heard.0.push("wall"))
+        .add_observer(|damaged: On<BrickDamaged>, mut heard: ResMut<Heard>| {
+            assert_eq!(damaged.kind, BrickKind::Steel);
+            assert_eq!(damaged.remaining, None);
+            assert!(damaged.speed.is_some());
+            heard.0.push("brick damaged")
+        })
+        .add_observer(|destroyed: On<BrickDestroyed>, mut heard: ResMut<Heard>| {
+            assert_eq!(destroyed.kind, BrickKind::Normal);
+            heard.0.push("brick destroyed")
+        })
+        .add_observer(|_: On<LifeLost>, mut heard: ResMut<Heard>| heard.0.push("life lost"))
+        .add_observer(|_: On<LevelCleared>, mut heard: ResMut<Heard>| heard.0.push("level cleared"));
+}
+
+#[test]
+fn collisions_trigger_typed_events() {
+    let mut harness = Harness::new(&["#"]);
+    listen(&mut harness);
+    harness.launch();
+    let ticks = harness.run_until(200, |harness| harness.game_state() == GameState::Victory);
+    assert!(ticks.is_some(), "小球应该击中正上方的砖块");
+    assert_eq!(harness.app.world().resource::<Heard>().0, ["launch", "brick destroyed", "level cleared"]);
+
+    // 钢铁砖块只受损，小球反弹回来碰到挡板
+    let mut harness = Harness::new(&["S"]);
+    listen(&mut harness);
+    harness.launch();
+    let ticks = harness.run_until(300, |harness| harness.app.world().resource::<Heard>().0.len() >= 3);
+    assert!(ticks.is_some(), "小球应该从钢铁砖块反弹回挡板");
+    assert_eq!(harness.app.world().resource::<Heard>().0, ["launch", "brick damaged", "paddle"]);
+
+    // 场上最后一个小球掉落
+    let below_paddle = harness.paddle_position() - Vec2::new(0.0, 40.0);
+    harness.place_ball(below_paddle, Vec2::new(0.0, -BALL_SPEED));
+    let ticks = harness.run_until(60, |harness| harness.lives() == 2);
+    assert!(ticks.is_some(), "小球掉落后应该失去一条生命");
+    assert_eq!(harness.app.world().resource::<Heard>().0.last(), Some(&"life lost"));
+}
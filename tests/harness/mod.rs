@@ -0,0 +1,366 @@
+//! 无窗口的游戏模拟测试工具
+//! 在 `MinimalPlugins` 下运行 [`GameplayPlugin`]，按固定时间步长逐帧推进，
+//! 通过键盘事件模拟玩家输入，并读取 `Score`、`Lives`、`GameState` 等资源进行断言
+
+// 不同的测试文件只会用到部分辅助函数
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::input::gamepad::{
+    GamepadSettings, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::state::app::StatesPlugin;
+use bevy::text::TextIterScratch;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::WindowFocused;
+use breakout::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// 每次 `update` 推进的时间，与默认的固定时间步长（64Hz）一致，保证每帧恰好运行一次 FixedUpdate
+pub const TICK: Duration = Duration::from_micros(15_625);
+
+/// 游戏模拟器
+pub struct Harness {
+    pub app: App,
+}
+
+impl Harness {
+    /// 使用给定的砖块网格创建模拟器，并进入游戏进行中状态
+    ///
+    /// # 参数
+    /// - `grid`: 关卡网格，字符含义与关卡文件相同（见 `BrickKind::from_cell`）
+    pub fn new(grid: &[&str]) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, InputPlugin, AssetPlugin::default()))
+            // 小球精灵图需要的资产类型（没有渲染插件时不会自动注册）
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            // 界面主题加载界面字体
+            .init_asset::<Font>()
+            // 记分板通过 TextUiWriter 更新文本，需要 TextPlugin 提供的缓存资源
+            .init_resource::<TextIterScratch>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+            .add_plugins((WidgetPlugin, GameplayPlugin, SavePlugin, HighScorePlugin))
+            // 不读写玩家真实的存档和排行榜文件，只保存在内存中
+            .insert_resource(SaveFile(None))
+            .insert_resource(SavedRun::default())
+            .insert_resource(HighScoreFile(None))
+            .insert_resource(HighScores::default())
+            .insert_resource(LevelCatalog {
+                levels: vec![LevelData {
+                    name: "test".to_string(),
+                    grid: grid.iter().map(|row| row.to_string()).collect(),
+                }],
+            })
+            // 固定随机种子，保证道具掉落可复现
+            .insert_resource(GameRng(StdRng::seed_from_u64(0)));
+
+        let mut harness = Harness { app };
+        harness.set_state(GameState::Playing);
+        harness
+    }
+
+    /// 切换游戏状态，并运行一帧使状态切换生效
+    pub fn set_state(&mut self, state: GameState) {
+        self.app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+        self.tick();
+    }
+
+    /// 运行一帧
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    /// 修改每帧推进的时间（同时修改固定时间步长），用于模拟卡顿时的大步长
+    pub fn set_tick(&mut self, tick: Duration) {
+        self.app.insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+        self.app.world_mut().resource_mut::<Time<Fixed>>().set_timestep(tick);
+    }
+
+    /// 运行多帧
+    pub fn run(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// 逐帧运行直到条件满足
+    ///
+    /// # 返回值
+    /// 条件满足时返回已运行的帧数，超过 `max_ticks` 仍未满足时返回 None
+    pub fn run_until(&mut self, max_ticks: usize, mut condition: impl FnMut(&mut Self) -> bool) -> Option<usize> {
+        for tick in 0..max_ticks {
+            if condition(self) {
+                return Some(tick);
+            }
+            self.tick();
+        }
+        condition(self).then_some(max_ticks)
+    }
+
+    /// 按下按键（下一帧生效）
+    pub fn press(&mut self, key_code: KeyCode) {
+        self.send_key(key_code, ButtonState::Pressed);
+    }
+
+    /// 松开按键（下一帧生效）
+    pub fn release(&mut self, key_code: KeyCode) {
+        self.send_key(key_code, ButtonState::Released);
+    }
+
+    /// 按下并松开按键，各运行一帧
+    pub fn tap(&mut self, key_code: KeyCode) {
+        self.press(key_code);
+        self.tick();
+        self.release(key_code);
+        self.tick();
+    }
+
+    /// 发送键盘事件，由 InputPlugin 更新 `ButtonInput<KeyCode>`
+    fn send_key(&mut self, key_code: KeyCode, state: ButtonState) {
+        self.app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    /// 修改输入设置（选择输入设备、鼠标灵敏度）
+    pub fn set_input_settings(&mut self, settings: InputSettings) {
+        self.app.insert_resource(settings);
+    }
+
+    /// 把动作重新绑定到一个按键
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        self.app.world_mut().resource_mut::<KeyBindings>().rebind(action, key);
+    }
+
+    /// 连接一个手柄
+    pub fn connect_gamepad(&mut self) -> Entity {
+        self.app
+            .world_mut()
+            .spawn((Gamepad::default(), GamepadSettings::default()))
+            .id()
+    }
+
+    /// 设置手柄摇杆或扳机的位置（下一帧生效）
+    pub fn move_gamepad_axis(&mut self, gamepad: Entity, axis: GamepadAxis, value: f32) {
+        self.app
+            .world_mut()
+            .write_message(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(gamepad, axis, value)));
+    }
+
+    /// 按下或松开手柄按键（下一帧生效）
+    pub fn set_gamepad_button(&mut self, gamepad: Entity, button: GamepadButton, pressed: bool) {
+        let value = if pressed { 1.0 } else { 0.0 };
+        self.app
+            .world_mut()
+            .write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(gamepad, button, value)));
+    }
+
+    /// 按下并松开手柄按键，各运行一帧
+    pub fn tap_gamepad_button(&mut self, gamepad: Entity, button: GamepadButton) {
+        for pressed in [true, false] {
+            self.set_gamepad_button(gamepad, button, pressed);
+            self.tick();
+        }
+    }
+
+    /// 设置指针（鼠标或触摸）在游戏世界中的横坐标
+    ///
+    /// 无窗口运行时没有光标，直接修改 `PointerTarget`
+    pub fn set_pointer(&mut self, x: Option<f32>) {
+        self.app.world_mut().resource_mut::<PointerTarget>().0 = x;
+    }
+
+    /// 按下并松开鼠标左键，各运行一帧
+    pub fn click(&mut self) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world_mut().write_message(MouseButtonInput {
+                button: MouseButton::Left,
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            self.tick();
+        }
+    }
+
+    /// 生成一个界面按钮，位置为按钮中心的界面坐标（无窗口运行时没有布局，直接指定位置）
+    pub fn spawn_button(&mut self, x: f32, y: f32) -> Entity {
+        self.app
+            .world_mut()
+            .spawn((Button, UiGlobalTransform::from_xy(x, y), InheritedVisibility::VISIBLE))
+            .id()
+    }
+
+    /// 按钮的交互状态
+    pub fn interaction(&self, button: Entity) -> Interaction {
+        *self.app.world().get::<Interaction>(button).unwrap()
+    }
+
+    /// 在关卡目录末尾添加一个关卡
+    pub fn add_level(&mut self, grid: &[&str]) {
+        let mut catalog = self.app.world_mut().resource_mut::<LevelCatalog>();
+        let name = format!("test {}", catalog.len() + 1);
+        catalog.levels.push(LevelData {
+            name,
+            grid: grid.iter().map(|row| row.to_string()).collect(),
+        });
+    }
+
+    /// 运行一次系统（模拟界面按钮的操作），状态切换在下一帧生效
+    pub fn run_system<M>(&mut self, system: impl IntoSystem<(), (), M>) {
+        self.app.world_mut().run_system_once(system).expect("系统参数应该都存在");
+    }
+
+    /// 当前关卡索引
+    pub fn level(&self) -> usize {
+        self.app.world().resource::<Level>().0
+    }
+
+    /// 本局游戏进度
+    pub fn run_progress(&self) -> RunProgress {
+        self.app.world().resource::<RunProgress>().clone()
+    }
+
+    /// 当前分数
+    pub fn score(&self) -> usize {
+        self.app.world().resource::<Score>().0
+    }
+
+    /// 当前生命数
+    pub fn lives(&self) -> usize {
+        self.app.world().resource::<Lives>().0
+    }
+
+    /// 设置生命数
+    pub fn set_lives(&mut self, lives: usize) {
+        self.app.world_mut().resource_mut::<Lives>().0 = lives;
+    }
+
+    /// 当前游戏状态
+    pub fn game_state(&self) -> GameState {
+        *self.app.world().resource::<State<GameState>>().get()
+    }
+
+    /// 当前游戏进行中状态
+    pub fn playing_state(&self) -> PlayingState {
+        *self.app.world().resource::<State<PlayingState>>().get()
+    }
+
+    /// 当前暂停状态，不在游戏中时为 None
+    pub fn pause_state(&self) -> Option<PauseState> {
+        self.app.world().get_resource::<State<PauseState>>().map(|state| *state.get())
+    }
+
+    /// 模拟窗口获得或失去焦点（下一帧生效）
+    pub fn set_focus(&mut self, focused: bool) {
+        self.app.world_mut().write_message(WindowFocused {
+            window: Entity::PLACEHOLDER,
+            focused,
+        });
+    }
+
+    /// 剩余可摧毁砖块数量
+    pub fn remaining_bricks(&self) -> usize {
+        self.app.world().resource::<RemainingBricks>().0
+    }
+
+    /// 挡板位置
+    pub fn paddle_position(&mut self) -> Vec2 {
+        let mut query = self.app.world_mut().query_filtered::<&Transform, With<Paddle>>();
+        query.single(self.app.world()).unwrap().translation.truncate()
+    }
+
+    /// 场上所有小球的位置和速度（附着在挡板上的小球速度为 None）
+    pub fn balls(&mut self) -> Vec<(Vec2, Option<Vec2>)> {
+        let mut query = self
+            .app
+            .world_mut()
+            .query_filtered::<(&Transform, Option<&Velocity>, Has<BallAttached>), With<Ball>>();
+        query
+            .iter(self.app.world())
+            .map(|(transform, velocity, attached)| {
+                let velocity = if attached { None } else { velocity.map(|velocity| velocity.0) };
+                (transform.translation.truncate(), velocity)
+            })
+            .collect()
+    }
+
+    /// 把唯一的小球放到指定位置并设置速度（测试特定的碰撞场景）
+    pub fn place_ball(&mut self, position: Vec2, velocity: Vec2) {
+        let mut query = self
+            .app
+            .world_mut()
+            .query_filtered::<(&mut Transform, &mut Velocity), With<Ball>>();
+        let (mut transform, mut ball_velocity) = query.single_mut(self.app.world_mut()).unwrap();
+        transform.translation = position.extend(transform.translation.z);
+        ball_velocity.0 = velocity;
+    }
+
+    /// 发射附着在挡板上的小球
+    pub fn launch(&mut self) {
+        self.tap(KeyCode::ArrowUp);
+    }
+
+    /// 记录当前这局游戏的快照
+    pub fn capture(&mut self) -> RunSnapshot {
+        capture_run(self.app.world_mut()).expect("游戏进行中应该能生成快照")
+    }
+
+    /// 回到菜单，再从快照继续游戏（与主菜单的“继续游戏”按钮相同）
+    pub fn resume(&mut self, snapshot: RunSnapshot) {
+        self.set_state(GameState::Menu);
+        self.run_system(
+            move |mut commands: Commands,
+                  mut level: ResMut<Level>,
+                  mut next_state: ResMut<NextState<GameState>>| {
+                resume_run(&mut commands, &snapshot, &mut level, &mut next_state);
+            },
+        );
+        self.tick();
+    }
+
+    /// 当前内存中的存档
+    pub fn saved_run(&self) -> Option<RunSnapshot> {
+        self.app.world().resource::<SavedRun>().0.clone()
+    }
+
+    /// 当前内存中的排行榜
+    pub fn high_scores(&self) -> HighScores {
+        self.app.world().resource::<HighScores>().clone()
+    }
+
+    /// 设置当前存档（模拟启动时从文件读取的存档）
+    pub fn set_saved_run(&mut self, snapshot: Option<RunSnapshot>) {
+        self.app.world_mut().resource_mut::<SavedRun>().0 = snapshot;
+    }
+
+    /// 当前这局游戏正在录制的录像
+    pub fn recording(&self) -> Replay {
+        self.app.world().resource::<ReplayRecorder>().0.clone().expect("游戏进行中应该正在录制")
+    }
+
+    /// 回到菜单，再回放录像
+    pub fn play_back(&mut self, replay: Replay) {
+        self.set_state(GameState::Menu);
+        self.app.world_mut().resource_mut::<Level>().0 = replay.level;
+        self.app.world_mut().insert_resource(ReplayPlayback::new(replay));
+        self.set_state(GameState::Playing);
+    }
+
+    /// 已经回放的固定时间步长数量，不在回放时为 None
+    pub fn playback_tick(&self) -> Option<usize> {
+        self.app.world().get_resource::<ReplayPlayback>().map(ReplayPlayback::tick)
+    }
+}